@@ -0,0 +1,218 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Error, Expr, GenericArgument, LitInt, PathArguments, Type};
+
+/// The name of the helper attribute
+const ATTRIBUTE: &str = "asn1_der";
+
+/// The container kind of a struct
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Container {
+    /// The struct is encoded as `SEQUENCE`
+    Sequence,
+    /// The struct is encoded as `SET`
+    Set,
+}
+impl Container {
+    /// Parses the container attributes
+    pub fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut container = Container::Sequence;
+        for attr in attrs.iter().filter(|a| a.path().is_ident(ATTRIBUTE)) {
+            attr.parse_nested_meta(|meta| match meta.path.get_ident() {
+                Some(ident) if ident == "sequence" => {
+                    container = Container::Sequence;
+                    Ok(())
+                }
+                Some(ident) if ident == "set" => {
+                    container = Container::Set;
+                    Ok(())
+                }
+                _ => Err(meta.error("unsupported container attribute; expected `sequence` or `set`")),
+            })?;
+        }
+        Ok(container)
+    }
+
+    /// The universal tag of the container
+    pub fn tag(self) -> u8 {
+        match self {
+            Container::Sequence => 0x30,
+            Container::Set => 0x31,
+        }
+    }
+    /// The ASN.1 name of the container
+    pub fn name(self) -> &'static str {
+        match self {
+            Container::Sequence => "SEQUENCE",
+            Container::Set => "SET",
+        }
+    }
+}
+
+/// The tagging of a field
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Tagging {
+    /// The field is encoded with its own tag
+    Universal,
+    /// The field is wrapped into a context-specific tag (`[N] EXPLICIT`)
+    Explicit(u8),
+    /// The tag of the field is replaced with a context-specific tag (`[N] IMPLICIT`)
+    Implicit(u8),
+}
+
+/// Whether a field can be absent
+pub enum Presence {
+    /// The field must always be present
+    Required,
+    /// The field is an `OPTIONAL` component and of type `Option<T>`
+    Optional,
+    /// The field is a component with a `DEFAULT` value
+    Default(Expr),
+}
+
+/// The parsed attributes of a field or variant
+pub struct FieldAttributes {
+    /// The tagging of the field
+    pub tagging: Tagging,
+    /// Whether the field can be absent
+    pub presence: Presence,
+    /// Whether the field type is a `CHOICE` which implements `DerChoice` instead of `DerTagged`
+    pub choice: bool,
+}
+impl FieldAttributes {
+    /// Parses the field attributes
+    pub fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut this = Self { tagging: Tagging::Universal, presence: Presence::Required, choice: false };
+        for attr in attrs.iter().filter(|a| a.path().is_ident(ATTRIBUTE)) {
+            attr.parse_nested_meta(|meta| match meta.path.get_ident() {
+                Some(ident) if ident == "explicit" => this.set_tagging(Tagging::Explicit(tag_number(&meta)?), &meta),
+                Some(ident) if ident == "implicit" => this.set_tagging(Tagging::Implicit(tag_number(&meta)?), &meta),
+                Some(ident) if ident == "choice" => {
+                    this.choice = true;
+                    Ok(())
+                }
+                Some(ident) if ident == "optional" => this.set_presence(Presence::Optional, &meta),
+                Some(ident) if ident == "default" => {
+                    let default = match meta.input.peek(syn::Token![=]) {
+                        true => meta.value()?.parse()?,
+                        false => syn::parse_quote!(::core::default::Default::default()),
+                    };
+                    this.set_presence(Presence::Default(default), &meta)
+                }
+                _ => Err(meta.error(
+                    "unsupported field attribute; expected `explicit = N`, `implicit = N`, `choice`, `optional` or \
+                    `default`",
+                )),
+            })?;
+
+            // A `CHOICE` cannot be tagged implicitly since the tag is needed to select the alternative
+            if let (Tagging::Implicit(_), true) = (this.tagging, this.choice) {
+                return Err(Error::new_spanned(attr, "a `choice` cannot be tagged implicitly; use `explicit` instead"));
+            }
+        }
+        Ok(this)
+    }
+
+    /// Sets the tagging if it has not been set yet
+    fn set_tagging(&mut self, tagging: Tagging, meta: &ParseNestedMeta) -> Result<(), Error> {
+        match self.tagging {
+            Tagging::Universal => {
+                self.tagging = tagging;
+                Ok(())
+            }
+            _ => Err(meta.error("a field can only have one `explicit` or `implicit` tag")),
+        }
+    }
+    /// Sets the presence if it has not been set yet
+    fn set_presence(&mut self, presence: Presence, meta: &ParseNestedMeta) -> Result<(), Error> {
+        match self.presence {
+            Presence::Required => {
+                self.presence = presence;
+                Ok(())
+            }
+            _ => Err(meta.error("a field can either be `optional` or have a `default` value")),
+        }
+    }
+
+    /// The type that is actually de-/encoded for a value of `ty`
+    pub fn wire_type(&self, ty: &Type) -> TokenStream {
+        match self.tagging {
+            Tagging::Universal => quote!(#ty),
+            Tagging::Explicit(n) => quote!(::asn1_der::typed::Explicit<#n, #ty>),
+            Tagging::Implicit(n) => quote!(::asn1_der::typed::Implicit<#n, #ty>),
+        }
+    }
+    /// Whether the field is an untagged `CHOICE` that is recognized via `DerChoice`
+    pub fn is_untagged_choice(&self) -> bool {
+        self.choice && self.tagging == Tagging::Universal
+    }
+    /// The trait that is required on `ty` to recognize the field by its tag (if any)
+    pub fn tag_trait(&self) -> Option<TokenStream> {
+        match self.tagging {
+            Tagging::Explicit(_) => None,
+            Tagging::Universal if self.choice => Some(quote!(::asn1_der::typed::DerChoice)),
+            Tagging::Universal | Tagging::Implicit(_) => Some(quote!(::asn1_der::typed::DerTagged)),
+        }
+    }
+    /// Checks whether the `tag` matches the field of type `ty`
+    pub fn matches_tag(&self, ty: &Type, tag: TokenStream) -> TokenStream {
+        match self.is_untagged_choice() {
+            true => quote!(<#ty as ::asn1_der::typed::DerChoice>::matches_tag(#tag)),
+            false => {
+                let wire_type = self.wire_type(ty);
+                quote!(#tag == <#wire_type as ::asn1_der::typed::DerTagged>::TAG)
+            }
+        }
+    }
+    /// Reads the optional field of type `ty` from `reader`
+    pub fn read_optional(&self, ty: &Type) -> TokenStream {
+        let wire_type = self.wire_type(ty);
+        match self.is_untagged_choice() {
+            true => quote!(reader.read_if::<#wire_type, _>(<#ty as ::asn1_der::typed::DerChoice>::matches_tag)),
+            false => quote!(reader.read_optional::<#wire_type>()),
+        }
+    }
+
+    /// Wraps the reference `value` into the wire type
+    pub fn wrap_ref(&self, value: TokenStream) -> TokenStream {
+        match self.tagging {
+            Tagging::Universal => value,
+            Tagging::Explicit(n) => quote!(&::asn1_der::typed::Explicit::<#n, _>(#value)),
+            Tagging::Implicit(n) => quote!(&::asn1_der::typed::Implicit::<#n, _>(#value)),
+        }
+    }
+    /// Unwraps the wire type `value`
+    pub fn unwrap(&self, value: TokenStream) -> TokenStream {
+        match self.tagging {
+            Tagging::Universal => value,
+            Tagging::Explicit(_) | Tagging::Implicit(_) => quote!(#value.0),
+        }
+    }
+}
+
+/// Parses a context-specific tag number
+fn tag_number(meta: &ParseNestedMeta) -> Result<u8, Error> {
+    let literal: LitInt = meta.value()?.parse()?;
+    match literal.base10_parse::<u8>()? {
+        n if n <= 30 => Ok(n),
+        _ => Err(Error::new(literal.span(), "context-specific tag numbers above 30 are not supported")),
+    }
+}
+
+/// Gets `T` if `ty` is an `Option<T>`
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" && args.args.len() == 1 => {
+            match args.args.first()? {
+                GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
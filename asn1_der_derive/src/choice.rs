@@ -0,0 +1,317 @@
+use crate::attributes::{FieldAttributes, Presence, Tagging};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    parse_quote, DataEnum, DeriveInput, Error, Fields, GenericParam, Generics, Ident, Lifetime, LifetimeParam, LitStr,
+    Type, WherePredicate,
+};
+
+/// A parsed enum variant
+struct Variant {
+    /// The variant name
+    ident: Ident,
+    /// The type of the single unnamed field
+    ty: Type,
+    /// The variant attributes
+    attributes: FieldAttributes,
+}
+impl Variant {
+    /// A human readable description of the alternative for error messages
+    fn describe(&self) -> String {
+        let ty = &self.ty;
+        let ty = quote!(#ty).to_string();
+        let ty = [(" < ", "<"), (" <", "<"), (" >", ">"), (" , ", ", "), (" :: ", "::"), (":: ", "::"), ("& ", "&")]
+            .iter()
+            .fold(ty, |ty, (from, to)| ty.replace(from, to));
+        match self.attributes.tagging {
+            Tagging::Universal => format!("`{}` ({})", self.ident, ty),
+            Tagging::Explicit(n) => format!("`{}` ([{}] EXPLICIT {})", self.ident, n, ty),
+            Tagging::Implicit(n) => format!("`{}` ([{}] IMPLICIT {})", self.ident, n, ty),
+        }
+    }
+}
+
+/// A parsed enum that is encoded as `CHOICE`
+pub struct Choice {
+    /// The enum name
+    ident: Ident,
+    /// The enum generics
+    generics: Generics,
+    /// The variants
+    variants: Vec<Variant>,
+}
+impl Choice {
+    /// Parses an enum
+    pub fn parse(input: &DeriveInput, data: &DataEnum) -> Result<Self, Error> {
+        if data.variants.is_empty() {
+            return Err(Error::new_spanned(&input.ident, "a `CHOICE` needs at least one alternative"));
+        }
+
+        let mut variants = Vec::new();
+        for variant in data.variants.iter() {
+            let ty = match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed[0].ty.clone(),
+                _ => {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "`CHOICE` alternatives must have exactly one unnamed field",
+                    ))
+                }
+            };
+            let attributes = FieldAttributes::parse(&variant.attrs)?;
+            if !matches!(attributes.presence, Presence::Required) {
+                return Err(Error::new_spanned(variant, "`CHOICE` alternatives cannot be `optional` or `default`"));
+            }
+
+            // Alternatives are matched in order, so a duplicate tag would make the later alternative unreachable
+            if let Tagging::Explicit(number) | Tagging::Implicit(number) = attributes.tagging {
+                let duplicate = variants.iter().find(|other: &&Variant| {
+                    matches!(other.attributes.tagging, Tagging::Explicit(n) | Tagging::Implicit(n) if n == number)
+                });
+                if let Some(other) = duplicate {
+                    let message = format!(
+                        "`CHOICE` alternatives must have distinct tags; `[{}]` is already used by `{}`",
+                        number, other.ident
+                    );
+                    return Err(Error::new_spanned(variant, message));
+                }
+            }
+            variants.push(Variant { ident: variant.ident.clone(), ty, attributes });
+        }
+        Ok(Self { ident: input.ident.clone(), generics: input.generics.clone(), variants })
+    }
+
+    /// Creates an error message literal
+    fn message(&self, message: String) -> LitStr {
+        LitStr::new(&message, Span::call_site())
+    }
+    /// Asserts at compile time that the universally tagged alternatives have distinct tags
+    ///
+    /// _Note: Alternatives whose type depends on a type parameter of the enum cannot be checked in a constant, and
+    /// untagged `choice` alternatives have no single tag._
+    fn distinct_tags_assertion(&self) -> TokenStream {
+        let type_params: Vec<_> = self.generics.type_params().map(|param| param.ident.clone()).collect();
+        let universal: Vec<_> = (self.variants.iter())
+            .filter(|variant| variant.attributes.tagging == Tagging::Universal)
+            .filter(|variant| !variant.attributes.is_untagged_choice())
+            .filter_map(|variant| {
+                let ty = &variant.ty;
+                Some((variant, static_type(quote!(#ty), &type_params)?))
+            })
+            .collect();
+
+        let assertions = universal.iter().enumerate().flat_map(|(index, (first, first_ty))| {
+            universal[index + 1..].iter().map(move |(second, second_ty)| {
+                let message = self.message(format!(
+                    "`CHOICE` alternatives `{}` and `{}` of `{}` must have distinct tags",
+                    first.ident, second.ident, self.ident
+                ));
+                quote! {
+                    ::core::assert!(
+                        <#first_ty as ::asn1_der::typed::DerTagged>::TAG
+                            != <#second_ty as ::asn1_der::typed::DerTagged>::TAG,
+                        #message
+                    );
+                }
+            })
+        });
+        quote!(const _: () = { #( #assertions )* };)
+    }
+    /// Adds `predicates` to the where-clause of `generics` if the enum is generic over types
+    fn add_predicates(&self, generics: &mut Generics, predicates: impl Iterator<Item = WherePredicate>) {
+        if self.generics.type_params().next().is_some() {
+            generics.make_where_clause().predicates.extend(predicates);
+        }
+    }
+
+    /// Implements `DerEncodable`
+    pub fn derive_encodable(&self) -> TokenStream {
+        let ident = &self.ident;
+        let mut generics = self.generics.clone();
+        self.add_predicates(
+            &mut generics,
+            self.variants.iter().map(|variant| {
+                let ty = &variant.ty;
+                parse_quote!(#ty: ::asn1_der::typed::DerEncodable)
+            }),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        // Encode the selected alternative
+        let arms = self.variants.iter().map(|variant| {
+            let (variant_ident, value) = (&variant.ident, variant.attributes.wrap_ref(quote!(value)));
+            let message = self.message(format!("Failed to encode alternative `{}` of `{}`", variant_ident, ident));
+            quote! {
+                Self::#variant_ident(value) => ::asn1_der::ErrorChain::propagate(
+                    ::asn1_der::typed::DerEncodable::encode(#value, sink), ::asn1_der::e!(#message)
+                ),
+            }
+        });
+
+        let reverse_arms = self.variants.iter().map(|variant| {
+            let (variant_ident, value) = (&variant.ident, variant.attributes.wrap_ref(quote!(value)));
+            let message = self.message(format!("Failed to encode alternative `{}` of `{}`", variant_ident, ident));
+            quote! {
+                Self::#variant_ident(value) => ::asn1_der::ErrorChain::propagate(
+                    ::asn1_der::typed::DerEncodable::encode_reverse(#value, sink), ::asn1_der::e!(#message)
+                ),
+            }
+        });
+        let size_arms = self.variants.iter().map(|variant| {
+            let (variant_ident, value) = (&variant.ident, variant.attributes.wrap_ref(quote!(value)));
+            let message = self.message(format!("Failed to size alternative `{}` of `{}`", variant_ident, ident));
+            quote! {
+                Self::#variant_ident(value) => ::asn1_der::ErrorChain::propagate(
+                    ::asn1_der::typed::DerEncodable::encoded_len(#value), ::asn1_der::e!(#message)
+                ),
+            }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::asn1_der::typed::DerEncodable for #ident #ty_generics #where_clause {
+                fn encode<__S: ::asn1_der::Sink>(&self, sink: &mut __S)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    match self {
+                        #( #arms )*
+                    }
+                }
+                fn encoded_len(&self) -> ::core::result::Result<usize, ::asn1_der::Asn1DerError> {
+                    match self {
+                        #( #size_arms )*
+                    }
+                }
+                fn encode_reverse<__R: ::asn1_der::ReverseSink>(&self, sink: &mut __R)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    match self {
+                        #( #reverse_arms )*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Implements `DerDecodable` and `DerChoice`
+    pub fn derive_decodable(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        // The tag matchers require `DerTagged` or `DerChoice` on the alternatives
+        let mut choice_generics = self.generics.clone();
+        self.add_predicates(
+            &mut choice_generics,
+            self.variants.iter().filter_map(|variant| {
+                let (ty, tag_trait) = (&variant.ty, variant.attributes.tag_trait()?);
+                Some(parse_quote!(#ty: #tag_trait))
+            }),
+        );
+        let (impl_generics, ty_generics, where_clause) = choice_generics.split_for_impl();
+
+        // Use the first lifetime of the enum as decoding lifetime or introduce a new one
+        let mut generics = choice_generics.clone();
+        let lifetime = match self.generics.lifetimes().next() {
+            Some(param) => param.lifetime.clone(),
+            None => {
+                let lifetime = Lifetime::new("'__der", Span::call_site());
+                generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+                lifetime
+            }
+        };
+        self.add_predicates(
+            &mut generics,
+            self.variants.iter().map(|variant| {
+                let ty = &variant.ty;
+                parse_quote!(#ty: ::asn1_der::typed::DerDecodable<#lifetime>)
+            }),
+        );
+        let (decode_impl_generics, _, decode_where_clause) = generics.split_for_impl();
+
+        // Select the alternative by the tag
+        let matches = self.variants.iter().map(|variant| variant.attributes.matches_tag(&variant.ty, quote!(tag)));
+        let loaders = self.variants.iter().map(|variant| {
+            let (variant_ident, wire_type) = (&variant.ident, variant.attributes.wire_type(&variant.ty));
+            let matches_tag = variant.attributes.matches_tag(&variant.ty, quote!(object.tag()));
+            let unwrapped = variant.attributes.unwrap(quote!(value));
+            let message = self.message(format!("Failed to decode alternative `{}` of `{}`", variant_ident, ident));
+            quote! {
+                if #matches_tag {
+                    let value = ::asn1_der::ErrorChain::propagate(
+                        <#wire_type as ::asn1_der::typed::DerDecodable>::load_with(object, options),
+                        ::asn1_der::e!(#message)
+                    )?;
+                    return ::core::result::Result::Ok(Self::#variant_ident(#unwrapped));
+                }
+            }
+        });
+        let allocated_lens = self.variants.iter().map(|variant| {
+            let (variant_ident, ty) = (&variant.ident, &variant.ty);
+            quote! {
+                Self::#variant_ident(value) => {
+                    <#ty as ::asn1_der::typed::DerDecodable<#lifetime>>::allocated_len(value)
+                }
+            }
+        });
+        let alternatives: Vec<_> = self.variants.iter().map(Variant::describe).collect();
+        let distinct_tags = self.distinct_tags_assertion();
+        let unknown_message = self.message(format!(
+            "Unexpected tag for CHOICE `{}`; expected one of: {}",
+            ident,
+            alternatives.join(", ")
+        ));
+
+        quote! {
+            #distinct_tags
+            #[automatically_derived]
+            impl #impl_generics ::asn1_der::typed::DerChoice for #ident #ty_generics #where_clause {
+                fn matches_tag(tag: u8) -> bool {
+                    #( #matches )||*
+                }
+            }
+            #[automatically_derived]
+            impl #decode_impl_generics ::asn1_der::typed::DerDecodable<#lifetime> for #ident #ty_generics
+                #decode_where_clause
+            {
+                fn load(object: ::asn1_der::DerObject<#lifetime>)
+                    -> ::core::result::Result<Self, ::asn1_der::Asn1DerError>
+                {
+                    Self::load_with(object, ::asn1_der::DecodeOptions::STRICT)
+                }
+                fn load_with(object: ::asn1_der::DerObject<#lifetime>, options: ::asn1_der::DecodeOptions)
+                    -> ::core::result::Result<Self, ::asn1_der::Asn1DerError>
+                {
+                    #( #loaders )*
+                    ::core::result::Result::Err(::asn1_der::einval!(#unknown_message))
+                }
+                fn allocated_len(&self) -> usize {
+                    match self {
+                        #( #allocated_lens )*
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaces all lifetimes in `tokens` with `'static` or returns `None` if `tokens` depend on one of `type_params`
+fn static_type(tokens: TokenStream, type_params: &[Ident]) -> Option<TokenStream> {
+    let mut output = TokenStream::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let mut static_group = Group::new(group.delimiter(), static_type(group.stream(), type_params)?);
+                static_group.set_span(group.span());
+                output.extend([TokenTree::Group(static_group)]);
+            }
+            TokenTree::Ident(ident) if type_params.contains(&ident) => return None,
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                // Skip the name of the lifetime
+                tokens.next();
+                output.extend(quote!('static));
+            }
+            token => output.extend([token]),
+        }
+    }
+    Some(output)
+}
@@ -0,0 +1,132 @@
+//! This crate provides derive macros for the `DerEncodable` and `DerDecodable` traits of
+//! [`asn1_der`](https://crates.io/crates/asn1_der)
+//!
+//! _Note: You usually don't use this crate directly but enable the `derive` feature of `asn1_der`,
+//! which re-exports the macros as `asn1_der::typed::{DerEncodable, DerDecodable}`._
+//!
+//! ## Structs
+//! Structs are encoded as `SEQUENCE` where each field is a component in declaration order. With
+//! `#[asn1_der(set)]`, the struct is encoded as `SET` instead; the components are then sorted by
+//! their tags as required by DER.
+//!
+//! `#[derive(DerEncodable)]` implements `DerEncodable` and `DerContents`, `#[derive(DerDecodable)]`
+//! implements `DerDecodable` and `DerTagged`. If the struct has a lifetime parameter, the first
+//! lifetime is used as decoding lifetime, so fields can borrow from the decoded data (e.g.
+//! `Utf8String<'a>` or `DerObject<'a>`).
+//!
+//! The following field attributes are supported:
+//!  - `#[asn1_der(explicit = N)]`: The field is explicitly tagged with the context-specific tag `[N]`
+//!  - `#[asn1_der(implicit = N)]`: The field is implicitly tagged with the context-specific tag
+//!    `[N]`; this requires the field type to implement `DerTagged`
+//!  - `#[asn1_der(optional)]`: The field is an `OPTIONAL` component of type `Option<T>`, which is
+//!    omitted if it is `None`. The presence is detected by the tag, so `T` (or the explicit/implicit
+//!    tag) must be distinguishable from the following component.
+//!  - `#[asn1_der(default)]` or `#[asn1_der(default = EXPR)]`: The field is a component with the
+//!    `DEFAULT` value `T::default()` or `EXPR`. As required by DER, a value that is equal to the
+//!    default value is omitted during encoding, and an explicitly encoded default value is rejected
+//!    during decoding.
+//!  - `#[asn1_der(choice)]`: The field type is a `CHOICE` which implements `DerChoice` instead of
+//!    `DerTagged`; this is required for untagged `optional` or `default` choices and cannot be
+//!    combined with `implicit`.
+//!
+//! ```rust
+//! # use asn1_der::typed::{ DerDecodable, DerEncodable, Utf8String };
+//! #[derive(DerEncodable, DerDecodable)]
+//! struct Certificate<'a> {
+//!     #[asn1_der(explicit = 0, default)]
+//!     version: u8,
+//!     name: Utf8String<'a>,
+//!     #[asn1_der(implicit = 1, optional)]
+//!     comment: Option<Utf8String<'a>>,
+//! }
+//!
+//! let raw = b"\x30\x0f\xa0\x03\x02\x01\x02\x0c\x04test\x81\x02hi";
+//! let certificate = Certificate::decode(raw).unwrap();
+//! assert_eq!(certificate.version, 2);
+//! assert_eq!(certificate.name.get(), "test");
+//! assert_eq!(certificate.comment.as_ref().map(Utf8String::get), Some("hi"));
+//! ```
+//!
+//! ## Enums
+//! Enums are encoded as `CHOICE` where each variant is an alternative with exactly one unnamed
+//! field. During decoding, the alternative is selected by the tag of the object; an unknown tag is
+//! rejected with an error that lists the expected alternatives. The variants support the
+//! `explicit`, `implicit` and `choice` attributes; alternatives must have distinct tags.
+//!
+//! `#[derive(DerEncodable)]` implements `DerEncodable`, `#[derive(DerDecodable)]` implements
+//! `DerDecodable` and `DerChoice`.
+//!
+//! ```rust
+//! # use asn1_der::typed::{ DerDecodable, DerEncodable };
+//! #[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+//! enum Name {
+//!     Text(String),
+//!     #[asn1_der(implicit = 1)]
+//!     Email(String),
+//!     #[asn1_der(explicit = 2)]
+//!     Id(u64),
+//! }
+//!
+//! assert_eq!(Name::decode(b"\x0c\x04test").unwrap(), Name::Text("test".to_string()));
+//! assert_eq!(Name::decode(b"\x81\x03a@b").unwrap(), Name::Email("a@b".to_string()));
+//! assert_eq!(Name::decode(b"\xa2\x03\x02\x01\x07").unwrap(), Name::Id(7));
+//! assert!(Name::decode(b"\x05\x00").is_err());
+//! ```
+//!
+//! Since the alternatives are matched in order, alternatives with the same tag are rejected at
+//! compile time:
+//!
+//! ```rust,compile_fail
+//! # use asn1_der::typed::DerDecodable;
+//! #[derive(DerDecodable)]
+//! enum Name {
+//!     #[asn1_der(implicit = 1)]
+//!     Email(String),
+//!     #[asn1_der(explicit = 1)]
+//!     Id(u64),
+//! }
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use asn1_der::typed::DerDecodable;
+//! #[derive(DerDecodable)]
+//! enum Name<'a> {
+//!     Text(String),
+//!     Borrowed(&'a str),
+//! }
+//! ```
+
+mod attributes;
+mod choice;
+mod structs;
+
+use crate::choice::Choice;
+use crate::structs::Struct;
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, Data, DeriveInput, Error};
+
+/// Derives `DerEncodable` (and `DerContents` for structs); see the crate documentation for the supported
+/// attributes
+#[proc_macro_derive(DerEncodable, attributes(asn1_der))]
+pub fn der_encodable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let derived = match &input.data {
+        Data::Struct(data) => Struct::parse(&input, data).map(|s| s.derive_encodable()),
+        Data::Enum(data) => Choice::parse(&input, data).map(|c| c.derive_encodable()),
+        Data::Union(_) => Err(Error::new_spanned(&input.ident, "`DerEncodable` cannot be derived for unions")),
+    };
+    derived.unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Derives `DerDecodable` and `DerTagged` (or `DerChoice` for enums); see the crate documentation for the supported
+/// attributes
+#[proc_macro_derive(DerDecodable, attributes(asn1_der))]
+pub fn der_decodable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let derived = match &input.data {
+        Data::Struct(data) => Struct::parse(&input, data).map(|s| s.derive_decodable()),
+        Data::Enum(data) => Choice::parse(&input, data).map(|c| c.derive_decodable()),
+        Data::Union(_) => Err(Error::new_spanned(&input.ident, "`DerDecodable` cannot be derived for unions")),
+    };
+    derived.unwrap_or_else(Error::into_compile_error).into()
+}
@@ -0,0 +1,468 @@
+use crate::attributes::{option_inner, Container, FieldAttributes, Presence, Tagging};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, DataStruct, DeriveInput, Error, GenericParam, Generics, Ident, Lifetime, LifetimeParam, LitStr,
+    Member, Type, WherePredicate,
+};
+
+/// A parsed struct field
+struct Field {
+    /// The member to access the field
+    member: Member,
+    /// The local binding used during decoding
+    binding: Ident,
+    /// The value type (i.e. `T` for `OPTIONAL` fields of type `Option<T>`)
+    ty: Type,
+    /// The field attributes
+    attributes: FieldAttributes,
+}
+impl Field {
+    /// The name of the field for error messages
+    fn name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+    /// The type that is actually de-/encoded
+    fn wire_type(&self) -> TokenStream {
+        self.attributes.wire_type(&self.ty)
+    }
+    /// The trait that is required on the value type to determine the wire tag (if any)
+    fn tag_trait(&self, container: Container) -> Option<TokenStream> {
+        match (&self.attributes.tagging, &self.attributes.presence) {
+            (Tagging::Universal, Presence::Required) if container == Container::Sequence => None,
+            _ => self.attributes.tag_trait(),
+        }
+    }
+}
+
+/// A parsed struct
+pub struct Struct {
+    /// The struct name
+    ident: Ident,
+    /// The struct generics
+    generics: Generics,
+    /// The container kind
+    container: Container,
+    /// The fields
+    fields: Vec<Field>,
+}
+impl Struct {
+    /// Parses a struct
+    pub fn parse(input: &DeriveInput, data: &DataStruct) -> Result<Self, Error> {
+        let container = Container::parse(&input.attrs)?;
+        let mut fields = Vec::new();
+        for (index, field) in data.fields.iter().enumerate() {
+            let attributes = FieldAttributes::parse(&field.attrs)?;
+            if container == Container::Set && attributes.is_untagged_choice() {
+                // The components of a set are sorted by their tag, which is unknown for a `CHOICE`
+                return Err(Error::new_spanned(field, "a `choice` in a `set` must be tagged explicitly"));
+            }
+            let ty = match attributes.presence {
+                Presence::Optional => match option_inner(&field.ty) {
+                    Some(inner) => inner.clone(),
+                    None => return Err(Error::new_spanned(&field.ty, "`optional` fields must be of type `Option<T>`")),
+                },
+                _ => field.ty.clone(),
+            };
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            fields.push(Field { member, binding: format_ident!("__field{}", index), ty, attributes });
+        }
+        Ok(Self { ident: input.ident.clone(), generics: input.generics.clone(), container, fields })
+    }
+
+    /// Creates an error message literal
+    fn message(&self, message: String) -> LitStr {
+        LitStr::new(&message, Span::call_site())
+    }
+    /// Adds `predicates` to the where-clause of `generics` if the struct is generic over types
+    fn add_predicates(&self, generics: &mut Generics, predicates: impl Iterator<Item = WherePredicate>) {
+        if self.generics.type_params().next().is_some() {
+            generics.make_where_clause().predicates.extend(predicates);
+        }
+    }
+
+    /// Implements `DerEncodable` and `DerContents`
+    pub fn derive_encodable(&self) -> TokenStream {
+        let (ident, tag) = (&self.ident, self.container.tag());
+        let mut generics = self.generics.clone();
+        self.add_predicates(
+            &mut generics,
+            self.fields.iter().map(|field| {
+                let ty = &field.ty;
+                let mut bounds = quote!(::asn1_der::typed::DerEncodable);
+                if let Presence::Default(_) = field.attributes.presence {
+                    bounds.extend(quote!(+ ::core::cmp::PartialEq));
+                }
+                if let (Container::Set, Some(tag_trait)) = (self.container, field.tag_trait(self.container)) {
+                    bounds.extend(quote!(+ #tag_trait));
+                }
+                parse_quote!(#ty: #bounds)
+            }),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        // Generate the field encoders
+        let contents = self.encode_contents(false);
+        let contents_reverse = self.encode_contents(true);
+        let sizers = self.fields.iter().map(|field| self.size_field(field));
+        let encode_message = self.message(format!("Failed to encode `{}`", ident));
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::asn1_der::typed::DerContents for #ident #ty_generics #where_clause {
+                fn encode_contents<__S: ::asn1_der::Sink>(&self, sink: &mut __S)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    #contents
+                    ::core::result::Result::Ok(())
+                }
+                fn encode_contents_reverse<__R: ::asn1_der::ReverseSink>(&self, sink: &mut __R)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    #contents_reverse
+                    ::core::result::Result::Ok(())
+                }
+                fn contents_len(&self) -> ::core::result::Result<usize, ::asn1_der::Asn1DerError> {
+                    #[allow(unused_mut)]
+                    let mut len: usize = 0;
+                    #( #sizers )*
+                    ::core::result::Result::Ok(len)
+                }
+            }
+            #[automatically_derived]
+            impl #impl_generics ::asn1_der::typed::DerEncodable for #ident #ty_generics #where_clause {
+                fn encode<__S: ::asn1_der::Sink>(&self, sink: &mut __S)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    let writer = ::asn1_der::typed::ConstructedWriter::with_contents(#tag, self);
+                    ::asn1_der::ErrorChain::propagate(writer.write(sink), ::asn1_der::e!(#encode_message))
+                }
+                fn encoded_len(&self) -> ::core::result::Result<usize, ::asn1_der::Asn1DerError> {
+                    let writer = ::asn1_der::typed::ConstructedWriter::with_contents(#tag, self);
+                    ::asn1_der::ErrorChain::propagate(
+                        ::asn1_der::typed::DerEncodable::encoded_len(&writer), ::asn1_der::e!(#encode_message)
+                    )
+                }
+                fn encode_reverse<__R: ::asn1_der::ReverseSink>(&self, sink: &mut __R)
+                    -> ::core::result::Result<(), ::asn1_der::Asn1DerError>
+                {
+                    let writer = ::asn1_der::typed::ConstructedWriter::with_contents(#tag, self);
+                    ::asn1_der::ErrorChain::propagate(writer.write_reverse(sink), ::asn1_der::e!(#encode_message))
+                }
+            }
+        }
+    }
+    /// Generates the encoders for the fields in encoding order or, if `reverse` is set, in reverse
+    /// encoding order via `encode_reverse`
+    fn encode_contents(&self, reverse: bool) -> TokenStream {
+        let encoders: Vec<_> = self.fields.iter().map(|field| self.encode_field(field, reverse)).collect();
+        match (self.container, reverse) {
+            (Container::Sequence, false) => quote!(#( #encoders )*),
+            (Container::Sequence, true) => {
+                let encoders = encoders.iter().rev();
+                quote!(#( #encoders )*)
+            }
+            (Container::Set, _) => {
+                // DER requires the components of a set to be sorted by their tag class and number
+                let (count, indices): (_, Vec<_>) = (self.fields.len(), (0..self.fields.len()).collect());
+                let tags = self.fields.iter().map(|field| {
+                    let wire_type = field.wire_type();
+                    quote!(<#wire_type as ::asn1_der::typed::DerTagged>::TAG)
+                });
+                let order = match reverse {
+                    true => quote!(order.iter().rev()),
+                    false => quote!(order.iter()),
+                };
+                quote! {
+                    let mut order: [(u8, usize); #count] = [#( (#tags, #indices) ),*];
+                    order.sort_unstable_by_key(|(tag, _)| *tag & !0b0010_0000);
+                    for (_, index) in #order {
+                        match *index {
+                            #( #indices => { #encoders } )*
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// Generates the encoder for `field`
+    fn encode_field(&self, field: &Field, reverse: bool) -> TokenStream {
+        let message = self.message(format!("Failed to encode field `{}` of `{}`", field.name(), self.ident));
+        let encode = match reverse {
+            true => quote!(encode_reverse),
+            false => quote!(encode),
+        };
+        self.if_present(field, |value| {
+            let value = field.attributes.wrap_ref(value);
+            quote! {
+                ::asn1_der::ErrorChain::propagate(
+                    ::asn1_der::typed::DerEncodable::#encode(#value, sink), ::asn1_der::e!(#message)
+                )?;
+            }
+        })
+    }
+    /// Generates the code that adds the encoded length of `field` to `len`
+    fn size_field(&self, field: &Field) -> TokenStream {
+        let message = self.message(format!("Failed to size field `{}` of `{}`", field.name(), self.ident));
+        let overflow = self.message(format!("The length of `{}` would exceed `usize::max_value()`", self.ident));
+        self.if_present(field, |value| {
+            let value = field.attributes.wrap_ref(value);
+            quote! {
+                let field_len = ::asn1_der::ErrorChain::propagate(
+                    ::asn1_der::typed::DerEncodable::encoded_len(#value), ::asn1_der::e!(#message)
+                )?;
+                len = match len.checked_add(field_len) {
+                    ::core::option::Option::Some(len) => len,
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(::asn1_der::eunsupported!(#overflow));
+                    }
+                };
+            }
+        })
+    }
+    /// Wraps the code generated by `with_value` so that it is only executed if `field` is encoded
+    fn if_present(&self, field: &Field, with_value: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
+        let member = &field.member;
+        match &field.attributes.presence {
+            Presence::Required => with_value(quote!(&self.#member)),
+            Presence::Optional => {
+                let with_value = with_value(quote!(value));
+                quote!(if let ::core::option::Option::Some(value) = &self.#member { #with_value })
+            }
+            Presence::Default(default) => {
+                // DER forbids the encoding of a value that is equal to its default value
+                let (ty, with_value) = (&field.ty, with_value(quote!(&self.#member)));
+                quote! {
+                    let default: #ty = #default;
+                    if self.#member != default { #with_value }
+                }
+            }
+        }
+    }
+
+    /// Implements `DerDecodable` and `DerTagged`
+    pub fn derive_decodable(&self) -> TokenStream {
+        let (ident, tag) = (&self.ident, self.container.tag());
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        // Use the first lifetime of the struct as decoding lifetime or introduce a new one
+        let mut generics = self.generics.clone();
+        let lifetime = match self.generics.lifetimes().next() {
+            Some(param) => param.lifetime.clone(),
+            None => {
+                let lifetime = Lifetime::new("'__der", Span::call_site());
+                generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+                lifetime
+            }
+        };
+        self.add_predicates(
+            &mut generics,
+            self.fields.iter().map(|field| {
+                let ty = &field.ty;
+                let mut bounds = quote!(::asn1_der::typed::DerDecodable<#lifetime>);
+                if let Presence::Default(_) = field.attributes.presence {
+                    bounds.extend(quote!(+ ::core::cmp::PartialEq));
+                }
+                if let Some(tag_trait) = field.tag_trait(self.container) {
+                    bounds.extend(quote!(+ #tag_trait));
+                }
+                parse_quote!(#ty: #bounds)
+            }),
+        );
+        let (decode_impl_generics, _, decode_where_clause) = generics.split_for_impl();
+
+        // Generate the field decoders
+        let decoders = match self.container {
+            Container::Sequence => self.decode_sequence(),
+            Container::Set => self.decode_set(),
+        };
+        let (members, bindings) = (self.fields.iter().map(|f| &f.member), self.fields.iter().map(|f| &f.binding));
+        let allocated_lens = self.fields.iter().map(|field| {
+            let (member, ty) = (&field.member, &field.ty);
+            let allocated_len = quote!(<#ty as ::asn1_der::typed::DerDecodable<#lifetime>>::allocated_len);
+            match field.attributes.presence {
+                Presence::Optional => quote!(self.#member.as_ref().map_or(0, #allocated_len)),
+                _ => quote!(#allocated_len(&self.#member)),
+            }
+        });
+        let tag_message = self.message(format!("DER object is not a valid `{}` {}", ident, self.container.name()));
+        let nested_message = self.message(format!("Failed to decode `{}`", ident));
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::asn1_der::typed::DerTagged for #ident #ty_generics #where_clause {
+                const TAG: u8 = #tag;
+            }
+            #[automatically_derived]
+            impl #decode_impl_generics ::asn1_der::typed::DerDecodable<#lifetime> for #ident #ty_generics
+                #decode_where_clause
+            {
+                fn load(object: ::asn1_der::DerObject<#lifetime>)
+                    -> ::core::result::Result<Self, ::asn1_der::Asn1DerError>
+                {
+                    Self::load_with(object, ::asn1_der::DecodeOptions::STRICT)
+                }
+                fn load_with(object: ::asn1_der::DerObject<#lifetime>, options: ::asn1_der::DecodeOptions)
+                    -> ::core::result::Result<Self, ::asn1_der::Asn1DerError>
+                {
+                    if object.tag() != #tag {
+                        return ::core::result::Result::Err(::asn1_der::einval!(#tag_message));
+                    }
+                    let options = ::asn1_der::ErrorChain::propagate(options.nested(), ::asn1_der::e!(#nested_message))?;
+                    #[allow(unused_mut)]
+                    let mut reader = ::asn1_der::typed::SequenceReader::with_options(object.value(), options);
+                    #decoders
+                    ::core::result::Result::Ok(Self { #( #members: #bindings ),* })
+                }
+                fn allocated_len(&self) -> usize {
+                    let len: usize = 0;
+                    #( let len = len.saturating_add(#allocated_lens); )*
+                    len
+                }
+            }
+        }
+    }
+    /// Generates the decoders for the fields of a `SEQUENCE`
+    fn decode_sequence(&self) -> TokenStream {
+        let decoders = self.fields.iter().map(|field| {
+            let (binding, wire_type) = (&field.binding, field.wire_type());
+            let message = self.message(format!("Failed to decode field `{}` of `{}`", field.name(), self.ident));
+            let unwrapped = field.attributes.unwrap(quote!(value));
+            let read_optional = field.attributes.read_optional(&field.ty);
+            match &field.attributes.presence {
+                Presence::Required => quote! {
+                    let value = ::asn1_der::ErrorChain::propagate(
+                        reader.read::<#wire_type>(), ::asn1_der::e!(#message)
+                    )?;
+                    let #binding = #unwrapped;
+                },
+                Presence::Optional => quote! {
+                    let #binding = ::asn1_der::ErrorChain::propagate(
+                        #read_optional, ::asn1_der::e!(#message)
+                    )?.map(|value| #unwrapped);
+                },
+                Presence::Default(_) => {
+                    let value = quote! {
+                        ::asn1_der::ErrorChain::propagate(
+                            #read_optional, ::asn1_der::e!(#message)
+                        )?
+                    };
+                    let defaulted = self.defaulted(field, value);
+                    quote!(let #binding = #defaulted;)
+                }
+            }
+        });
+
+        let message = self.message(format!("Failed to decode `{}`", self.ident));
+        quote! {
+            #( #decoders )*
+            ::asn1_der::ErrorChain::propagate(reader.finish(), ::asn1_der::e!(#message))?;
+        }
+    }
+    /// Generates the decoders for the fields of a `SET`
+    fn decode_set(&self) -> TokenStream {
+        let message = self.message(format!("Failed to decode `{}`", self.ident));
+        if self.fields.is_empty() {
+            return quote!(::asn1_der::ErrorChain::propagate(reader.finish(), ::asn1_der::e!(#message))?;);
+        }
+
+        // Collect the components by tag
+        let slots = self.fields.iter().map(|field| {
+            let (binding, wire_type) = (&field.binding, field.wire_type());
+            quote!(let mut #binding: ::core::option::Option<#wire_type> = ::core::option::Option::None;)
+        });
+        let matchers = self.fields.iter().map(|field| {
+            let binding = &field.binding;
+            let message = self.message(format!("Failed to decode field `{}` of `{}`", field.name(), self.ident));
+            let (matches_tag, wire_type) = (field.attributes.matches_tag(&field.ty, quote!(tag)), field.wire_type());
+            quote! {
+                if #matches_tag {
+                    let value = reader.read::<#wire_type>();
+                    #binding = ::core::option::Option::Some(::asn1_der::ErrorChain::propagate(
+                        value, ::asn1_der::e!(#message)
+                    )?);
+                    continue;
+                }
+            }
+        });
+
+        // Apply the presence rules
+        let finalizers = self.fields.iter().map(|field| {
+            let binding = &field.binding;
+            let unwrapped = field.attributes.unwrap(quote!(value));
+            match &field.attributes.presence {
+                Presence::Required => {
+                    let message = self.message(format!("Missing component `{}` in `{}`", field.name(), self.ident));
+                    quote! {
+                        let #binding = match #binding {
+                            ::core::option::Option::Some(value) => #unwrapped,
+                            ::core::option::Option::None => {
+                                return ::core::result::Result::Err(::asn1_der::einval!(#message));
+                            }
+                        };
+                    }
+                }
+                Presence::Optional => quote!(let #binding = #binding.map(|value| #unwrapped);),
+                Presence::Default(_) => {
+                    let defaulted = self.defaulted(field, quote!(#binding));
+                    quote!(let #binding = #defaulted;)
+                }
+            }
+        });
+
+        let order_message = self.message(format!("The components of `{}` are not in canonical DER order", self.ident));
+        let unexpected_message = self.message(format!("Unexpected component in `{}`", self.ident));
+        quote! {
+            #( #slots )*
+            let mut last_key: ::core::option::Option<u8> = ::core::option::Option::None;
+            while !reader.is_empty() {
+                let tag = match ::asn1_der::ErrorChain::propagate(reader.peek(), ::asn1_der::e!(#message))? {
+                    ::core::option::Option::Some(subobject) => subobject.tag(),
+                    ::core::option::Option::None => break,
+                };
+
+                // DER requires the components of a set to be sorted by their tag class and number
+                let key = tag & !0b0010_0000;
+                if matches!(last_key, ::core::option::Option::Some(last) if key <= last) {
+                    return ::core::result::Result::Err(::asn1_der::einval!(#order_message));
+                }
+                last_key = ::core::option::Option::Some(key);
+
+                #( #matchers )*
+                return ::core::result::Result::Err(::asn1_der::einval!(#unexpected_message));
+            }
+            #( #finalizers )*
+        }
+    }
+    /// Generates an expression that applies the `DEFAULT` rules to the optional wire value `value`
+    fn defaulted(&self, field: &Field, value: TokenStream) -> TokenStream {
+        let (ty, unwrapped) = (&field.ty, field.attributes.unwrap(quote!(value)));
+        let default = match &field.attributes.presence {
+            Presence::Default(default) => default,
+            _ => unreachable!("Field has no default value"),
+        };
+        let message =
+            self.message(format!("Field `{}` of `{}` explicitly encodes its DEFAULT value", field.name(), self.ident));
+
+        // DER forbids the encoding of a value that is equal to its default value
+        quote! {
+            match #value {
+                ::core::option::Option::Some(value) => {
+                    let (value, default): (#ty, #ty) = (#unwrapped, #default);
+                    if value == default {
+                        return ::core::result::Result::Err(::asn1_der::einval!(#message));
+                    }
+                    value
+                }
+                ::core::option::Option::None => #default,
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+#![cfg_attr(test, deny(warnings))]
+
+use asn1_der::typed::{DerDecodable, DerEncodable, Utf8String};
+use asn1_der::{Asn1DerErrorVariant, DecodeLimits, DecodeOptions, DerObject, ReverseSliceSink};
+
+/// Encodes `value` into a new vector
+fn encode<T: DerEncodable>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.encode(&mut bytes).expect("Failed to encode value");
+    assert_eq!(value.encoded_len().expect("Failed to size value"), bytes.len());
+
+    let mut reverse = vec![0; bytes.len()];
+    value.encode_reverse(&mut ReverseSliceSink::new(&mut reverse)).expect("Failed to encode value back to front");
+    assert_eq!(reverse, bytes);
+    bytes
+}
+/// Ensures that the decoding of `bytes` as `T` fails with invalid data
+fn assert_invalid<'a, T: DerDecodable<'a>>(bytes: &'a [u8]) {
+    match T::decode(bytes) {
+        Err(e) if matches!(e.error, Asn1DerErrorVariant::InvalidData(_)) => (),
+        Err(e) => panic!("Unexpected error kind: {}", e),
+        Ok(_) => panic!("Unexpected success"),
+    }
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Simple {
+    number: u64,
+    flag: bool,
+    data: Vec<u8>,
+}
+const SIMPLE: &[u8] = b"\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03";
+
+#[test]
+fn sequence() {
+    let simple = Simple { number: 256, flag: true, data: vec![1, 2, 3] };
+    assert_eq!(encode(&simple), SIMPLE);
+    assert_eq!(Simple::decode(SIMPLE).expect("Failed to decode sequence"), simple);
+
+    // Invalid tag, missing and trailing components
+    assert_invalid::<Simple>(b"\x31\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03");
+    assert_invalid::<Simple>(b"\x30\x07\x02\x02\x01\x00\x01\x01\xff");
+    assert_invalid::<Simple>(b"\x30\x0e\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\x05\x00");
+
+    // Non-DER components are only accepted with lenient decode options
+    const LENIENT: &[u8] = b"\x30\x0e\x02\x03\x00\x01\x00\x01\x01\x01\x04\x81\x03\x01\x02\x03";
+    assert_invalid::<Simple>(LENIENT);
+    let decoded = Simple::decode_with(LENIENT, DecodeOptions::LENIENT).expect("Failed to decode sequence");
+    assert_eq!(decoded, simple);
+}
+
+#[derive(DerEncodable, DerDecodable)]
+struct Borrowed<'a> {
+    name: Utf8String<'a>,
+    any: DerObject<'a>,
+}
+
+#[test]
+fn borrowed() {
+    const BYTES: &[u8] = b"\x30\x08\x0c\x04test\x05\x00";
+
+    let borrowed = Borrowed::decode(BYTES).expect("Failed to decode sequence");
+    assert_eq!(borrowed.name.get(), "test");
+    assert_eq!(borrowed.any.raw(), b"\x05\x00");
+    assert_eq!(borrowed.any.raw().as_ptr(), BYTES[8..].as_ptr());
+    assert_eq!(encode(&borrowed), BYTES);
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Tagged {
+    #[asn1_der(explicit = 0)]
+    explicit: u8,
+    #[asn1_der(implicit = 1)]
+    implicit: u8,
+    #[asn1_der(implicit = 30)]
+    constructed: Simple,
+}
+
+#[test]
+fn tagged() {
+    const BYTES: &[u8] =
+        b"\x30\x16\xa0\x03\x02\x01\x07\x81\x01\x05\xbe\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03";
+
+    let tagged =
+        Tagged { explicit: 7, implicit: 5, constructed: Simple { number: 256, flag: true, data: vec![1, 2, 3] } };
+    assert_eq!(encode(&tagged), BYTES);
+    assert_eq!(Tagged::decode(BYTES).expect("Failed to decode tagged sequence"), tagged);
+
+    // Invalid explicit and implicit tags
+    assert_invalid::<Tagged>(
+        b"\x30\x16\xa1\x03\x02\x01\x07\x81\x01\x05\xbe\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03",
+    );
+    assert_invalid::<Tagged>(
+        b"\x30\x16\xa0\x03\x02\x01\x07\xa1\x01\x05\xbe\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03",
+    );
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Optionals {
+    #[asn1_der(optional)]
+    flag: Option<bool>,
+    number: u8,
+    #[asn1_der(implicit = 0, optional)]
+    first: Option<u8>,
+    #[asn1_der(explicit = 1, optional)]
+    second: Option<u8>,
+    nullable: Option<u8>,
+}
+
+#[test]
+fn optional() {
+    let all = Optionals { flag: Some(false), number: 4, first: Some(1), second: Some(2), nullable: Some(3) };
+    const ALL: &[u8] = b"\x30\x11\x01\x01\x00\x02\x01\x04\x80\x01\x01\xa1\x03\x02\x01\x02\x02\x01\x03";
+    assert_eq!(encode(&all), ALL);
+    assert_eq!(Optionals::decode(ALL).expect("Failed to decode sequence"), all);
+
+    // Absent optionals are omitted whereas `Option<T>` is encoded as `NULL`
+    let none = Optionals { flag: None, number: 4, first: None, second: Some(2), nullable: None };
+    const NONE: &[u8] = b"\x30\x0a\x02\x01\x04\xa1\x03\x02\x01\x02\x05\x00";
+    assert_eq!(encode(&none), NONE);
+    assert_eq!(Optionals::decode(NONE).expect("Failed to decode sequence"), none);
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Defaults {
+    #[asn1_der(default)]
+    version: u8,
+    #[asn1_der(default = true)]
+    flag: bool,
+    #[asn1_der(explicit = 0, default = 7)]
+    tagged: u8,
+}
+
+#[test]
+fn default() {
+    // Default values are omitted
+    let defaults = Defaults { version: 0, flag: true, tagged: 7 };
+    assert_eq!(encode(&defaults), b"\x30\x00");
+    assert_eq!(Defaults::decode(b"\x30\x00").expect("Failed to decode sequence"), defaults);
+
+    // Other values are encoded
+    let values = Defaults { version: 2, flag: false, tagged: 4 };
+    const VALUES: &[u8] = b"\x30\x0b\x02\x01\x02\x01\x01\x00\xa0\x03\x02\x01\x04";
+    assert_eq!(encode(&values), VALUES);
+    assert_eq!(Defaults::decode(VALUES).expect("Failed to decode sequence"), values);
+
+    // Explicitly encoded default values are rejected
+    assert_invalid::<Defaults>(b"\x30\x03\x02\x01\x00");
+    assert_invalid::<Defaults>(b"\x30\x03\x01\x01\xff");
+    assert_invalid::<Defaults>(b"\x30\x05\xa0\x03\x02\x01\x07");
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+#[asn1_der(set)]
+struct Set {
+    name: String,
+    #[asn1_der(implicit = 1)]
+    second: u8,
+    #[asn1_der(explicit = 0, optional)]
+    first: Option<u8>,
+    number: Simple,
+    flag: bool,
+}
+
+#[test]
+fn set() {
+    // The components are sorted by tag class and number (i.e. bool, string, sequence, [0], [1])
+    let set = Set {
+        name: "test".to_string(),
+        second: 2,
+        first: Some(1),
+        number: Simple { number: 256, flag: true, data: vec![1, 2, 3] },
+        flag: true,
+    };
+    const SET: &[u8] = b"\x31\x1f\x01\x01\xff\x0c\x04test\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\xa0\x03\x02\x01\x01\x81\x01\x02";
+    assert_eq!(encode(&set), SET);
+    assert_eq!(Set::decode(SET).expect("Failed to decode set"), set);
+
+    // Absent optional components
+    let set = Set { first: None, ..set };
+    const SET_NONE: &[u8] =
+        b"\x31\x1a\x01\x01\xff\x0c\x04test\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\x81\x01\x02";
+    assert_eq!(encode(&set), SET_NONE);
+    assert_eq!(Set::decode(SET_NONE).expect("Failed to decode set"), set);
+
+    // Non-canonical order, duplicate and missing components
+    assert_invalid::<Set>(
+        b"\x31\x1a\x0c\x04test\x01\x01\xff\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\x81\x01\x02",
+    );
+    assert_invalid::<Set>(b"\x31\x1d\x01\x01\xff\x0c\x04test\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\x81\x01\x02\x81\x01\x02");
+    assert_invalid::<Set>(b"\x31\x0c\x01\x01\xff\x0c\x04test\x81\x01\x02");
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Generic<T> {
+    value: T,
+    #[asn1_der(implicit = 0, optional)]
+    other: Option<T>,
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Tuple(u8, #[asn1_der(default)] bool);
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct Unit;
+
+#[test]
+fn generic_tuple_unit() {
+    let generic = Generic { value: 1u8, other: Some(2u8) };
+    assert_eq!(encode(&generic), b"\x30\x06\x02\x01\x01\x80\x01\x02");
+    assert_eq!(Generic::<u8>::decode(b"\x30\x06\x02\x01\x01\x80\x01\x02").expect("Failed to decode"), generic);
+
+    let tuple = Tuple(4, false);
+    assert_eq!(encode(&tuple), b"\x30\x03\x02\x01\x04");
+    assert_eq!(Tuple::decode(b"\x30\x03\x02\x01\x04").expect("Failed to decode"), tuple);
+
+    assert_eq!(encode(&Unit), b"\x30\x00");
+    assert_eq!(Unit::decode(b"\x30\x00").expect("Failed to decode"), Unit);
+    assert_invalid::<Unit>(b"\x30\x02\x05\x00");
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+enum Time {
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+enum Name {
+    #[asn1_der(implicit = 1)]
+    Email(String),
+    #[asn1_der(explicit = 4)]
+    Directory(Simple),
+    #[asn1_der(choice)]
+    Time(Time),
+}
+
+#[derive(Debug, PartialEq, DerEncodable, DerDecodable)]
+struct WithChoice {
+    #[asn1_der(choice, optional)]
+    time: Option<Time>,
+    flag: bool,
+    #[asn1_der(explicit = 0, choice)]
+    name: Name,
+}
+
+#[test]
+fn choice() {
+    // The alternative is selected by the tag
+    assert_eq!(encode(&Time::Number(5)), b"\x02\x01\x05");
+    assert_eq!(Time::decode(b"\x02\x01\x05").expect("Failed to decode choice"), Time::Number(5));
+    assert_eq!(encode(&Time::Text("hi".to_string())), b"\x0c\x02hi");
+    assert_eq!(Time::decode(b"\x0c\x02hi").expect("Failed to decode choice"), Time::Text("hi".to_string()));
+
+    // Tagged and nested alternatives
+    const EMAIL: &[u8] = b"\x81\x03a@b";
+    assert_eq!(Name::decode(EMAIL).expect("Failed to decode choice"), Name::Email("a@b".to_string()));
+    assert_eq!(encode(&Name::Email("a@b".to_string())), EMAIL);
+    const DIRECTORY: &[u8] = b"\xa4\x0e\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03";
+    let directory = Name::Directory(Simple { number: 256, flag: true, data: vec![1, 2, 3] });
+    assert_eq!(encode(&directory), DIRECTORY);
+    assert_eq!(Name::decode(DIRECTORY).expect("Failed to decode choice"), directory);
+    assert_eq!(encode(&Name::Time(Time::Number(5))), b"\x02\x01\x05");
+    assert_eq!(Name::decode(b"\x02\x01\x05").expect("Failed to decode choice"), Name::Time(Time::Number(5)));
+
+    // Unknown tags list the expected alternatives
+    assert_invalid::<Name>(b"\x01\x01\xff");
+    let error = Time::decode(b"\x01\x01\xff").expect_err("Unexpected success").to_string();
+    assert!(error.contains("expected one of: `Number` (u64), `Text` (String)"), "{}", error);
+    let error = Name::decode(b"\x80\x00").expect_err("Unexpected success").to_string();
+    assert!(error.contains("`Email` ([1] IMPLICIT String), `Directory` ([4] EXPLICIT Simple)"), "{}", error);
+}
+
+#[test]
+fn choice_field() {
+    let with_choice =
+        WithChoice { time: Some(Time::Text("hi".to_string())), flag: true, name: Name::Email("a@b".to_string()) };
+    const BYTES: &[u8] = b"\x30\x0e\x0c\x02hi\x01\x01\xff\xa0\x05\x81\x03a@b";
+    assert_eq!(encode(&with_choice), BYTES);
+    assert_eq!(WithChoice::decode(BYTES).expect("Failed to decode sequence"), with_choice);
+
+    // An absent optional choice is detected by its tags
+    let with_choice = WithChoice { time: None, flag: false, name: Name::Time(Time::Number(5)) };
+    const NONE: &[u8] = b"\x30\x08\x01\x01\x00\xa0\x03\x02\x01\x05";
+    assert_eq!(encode(&with_choice), NONE);
+    assert_eq!(WithChoice::decode(NONE).expect("Failed to decode sequence"), with_choice);
+}
+
+#[test]
+fn limits() {
+    type Nested = Generic<Generic<Unit>>;
+    const NESTED: &[u8] = b"\x30\x04\x30\x02\x30\x00";
+    let nested = Generic { value: Generic { value: Unit, other: None }, other: None };
+    assert_eq!(encode(&nested), NESTED);
+
+    // Each derived struct consumes one level of depth
+    let limits = DecodeLimits { max_depth: 3, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+    assert_eq!(Nested::decode_with(NESTED, options).expect("Failed to decode sequence"), nested);
+
+    let limits = DecodeLimits { max_depth: 2, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+    match Nested::decode_with(NESTED, options) {
+        Err(e) if matches!(e.error, Asn1DerErrorVariant::LimitExceeded(_)) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    // All components of a derived struct share the allocation budget (the name and the data take 7 bytes)
+    const SET: &[u8] =
+        b"\x31\x1a\x01\x01\xff\x0c\x04test\x30\x0c\x02\x02\x01\x00\x01\x01\xff\x04\x03\x01\x02\x03\x81\x01\x02";
+    let limits = DecodeLimits { max_alloc: 7, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+    Set::decode_with(SET, options).expect("Failed to decode set");
+
+    let limits = DecodeLimits { max_alloc: 6, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+    match Set::decode_with(SET, options) {
+        Err(e) if matches!(e.error, Asn1DerErrorVariant::LimitExceeded(_)) => (),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
@@ -1,31 +1,122 @@
 //! Dummy compile target for simple no-panic evaluation
 
-use asn1_der::{DerObject, Sink};
+use asn1_der::cer::{self, CerSink};
+use asn1_der::traversal::SliceTraversal;
+use asn1_der::{BerObject, DerObject, Header, IncrementalParser, PatchingEncoder, ReverseSliceSink, Sink, SliceSink};
 
 fn main() {
     /// An ASN.1-DER encoded integer `7`
-    const INT7: &'static [u8] = b"\x02\x01\x07";
+    const INT7: &[u8] = b"\x02\x01\x07";
 
     // Decode an arbitrary DER object
     let object = DerObject::decode(INT7).expect("Failed to decode object");
 
+    // Decode only the header of an object
+    let header = Header::decode(&INT7[..2]).expect("Failed to decode header");
+    assert_eq!(header.object_len().expect("Invalid header"), INT7.len());
+
+    // Walk a constructed object by its headers
+    let traversal = SliceTraversal::new(b"\x30\x03\x02\x01\x07");
+    let root = traversal.root().expect("Failed to decode root");
+    for child in traversal.children(&root).expect("Failed to walk root") {
+        traversal.object(&child.expect("Failed to decode child")).expect("Failed to load child");
+    }
+
     // Encode an arbitrary DER object
     let (mut buf, mut buf_len) = ([0; 4], 0);
     let mut sink = buf.iter_mut().counting_sink(&mut buf_len);
     object.encode(&mut sink).expect("Failed to encode object");
 
+    // Decode and reassemble a constructed BER string
+    let ber = BerObject::decode(b"\x24\x80\x04\x01\x07\x00\x00").expect("Failed to decode object");
+    let mut buf = [0; 1];
+    ber.copy_value(&mut buf.iter_mut()).expect("Failed to reassemble object");
+
+    // Encode an arbitrary DER object as CER and validate it
+    let mut buf = [0; 3];
+    let mut iter = buf.iter_mut();
+    let mut sink = CerSink::new(&mut iter);
+    object.encode(&mut sink).expect("Failed to encode object");
+    sink.finish().expect("Failed to finish object");
+    cer::validate(&buf).expect("Invalid CER object");
+
+    // Encode a nested constructed object with back-patched lengths
+    let (mut buf, mut buf_len) = ([0; 256], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut buf_len);
+    PatchingEncoder::new(&mut sink)
+        .sequence(|encoder| encoder.constructed(0xa0, |encoder| encoder.write(0x04, &[0; 200])))
+        .expect("Failed to encode object");
+
+    // Encode a nested constructed object back to front
+    let mut buf = [0; 256];
+    let mut sink = ReverseSliceSink::new(&mut buf);
+    DerObject::write_reverse(0x04, &[0; 200], &mut sink).expect("Failed to encode object");
+    DerObject::write_header_reverse(0x30, 203, &mut sink).expect("Failed to encode object");
+
+    // Split a stream into objects incrementally
+    let mut parser = IncrementalParser::new();
+    parser.feed(&INT7[..2]).expect("Failed to parse object");
+    parser.feed(&INT7[2..]).expect("Failed to parse object");
+
+    // Copy an object between `embedded-io` reader and writer
+    #[cfg(feature = "embedded_io")]
+    {
+        use asn1_der::{EmbeddedSink, EmbeddedSource};
+
+        let (mut buf, mut buf_len) = ([0; 4], 0);
+        let mut source = EmbeddedSource::new(INT7);
+        let object = DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut buf_len))
+            .expect("Failed to decode object");
+
+        let mut buf = [0; 4];
+        let mut sink = EmbeddedSink::new(buf.as_mut_slice());
+        object.encode(&mut sink).expect("Failed to encode object");
+    }
+
+    // Decode into and encode from fixed-capacity collections
+    #[cfg(all(feature = "heapless", feature = "native_types"))]
+    {
+        use asn1_der::typed::{DerDecodable, DerEncodable, HeaplessSequence};
+
+        let sequence = HeaplessSequence::<u8, 2>::decode(b"\x30\x03\x02\x01\x07").expect("Failed to decode sequence");
+        let mut buf: heapless::Vec<u8, 8> = heapless::Vec::new();
+        sequence.encode(&mut buf).expect("Failed to encode sequence");
+        heapless::Vec::<u8, 1>::decode(b"\x04\x01\x07").expect("Failed to decode octet string");
+    }
+
     // Simple integer testing
     #[cfg(feature = "native_types")]
     {
-        use asn1_der::typed::{DerDecodable, DerEncodable};
+        use asn1_der::typed::{Defaulted, DerDecodable, DerEncodable, SequenceReader};
 
         // Decode a `u8`
         let number = u8::decode(INT7).expect("Failed to decode number");
         assert_eq!(number, 7);
 
+        // Decode and encode a component with a `DEFAULT` value
+        let defaulted = Defaulted::<u8>::decode(INT7).expect("Failed to decode defaulted number");
+        let mut buf = [0; 4];
+        defaulted.encode(&mut buf.iter_mut()).expect("Failed to encode defaulted number");
+
+        // Read a present and an absent component with a `DEFAULT` value
+        let mut reader = SequenceReader::new(INT7);
+        let present: Defaulted<u8> = reader.read_defaulted().expect("Failed to read defaulted number");
+        assert_eq!(present.into_inner(), 7);
+        let absent: Defaulted<u8> = reader.read_defaulted().expect("Failed to read defaulted number");
+        assert!(absent.is_default());
+
+        // Decode an octet string without copying it
+        let bytes = <&[u8]>::decode(b"\x04\x01\x07").expect("Failed to decode octet string");
+        assert_eq!(bytes, &[7]);
+
         // Encode a new `u8`
         let (mut buf, mut buf_len) = ([0; 4], 0);
         let mut sink = buf.iter_mut().counting_sink(&mut buf_len);
         7u8.encode(&mut sink).expect("Failed to encode number");
+
+        // Encode a new `u8` back to front
+        let mut buf = [0; 4];
+        let number = 7u8.der_object_reverse(ReverseSliceSink::new(&mut buf)).expect("Failed to encode number");
+        assert_eq!(number.raw(), INT7);
     }
 }
@@ -0,0 +1,213 @@
+use crate::ber::{is_string, minimal_header_len, BerObject, BIT_STRING, CONSTRUCTED};
+use crate::der::length;
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, Sink};
+use alloc::vec::Vec;
+
+/// The tag of a BOOLEAN
+const BOOLEAN: u8 = 0x01;
+/// The tag of an INTEGER
+const INTEGER: u8 = 0x02;
+/// The tag of an ENUMERATED
+const ENUMERATED: u8 = 0x0a;
+/// The tag of a SET or SET OF
+const SET: u8 = 0x31;
+/// The maximum nesting depth of constructed objects
+const MAX_DEPTH: usize = 128;
+
+/// A transformation that was necessary to convert a BER object into DER
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Transformation {
+    /// A definite length was encoded with more bytes than necessary
+    NonMinimalLength,
+    /// The indefinite length form was replaced by a definite length
+    IndefiniteLength,
+    /// A constructed string was reassembled into a primitive string
+    ConstructedString,
+    /// Redundant leading bytes of an INTEGER or ENUMERATED were removed
+    NonMinimalInteger,
+    /// A BOOLEAN `TRUE` was not encoded as `0xff`
+    NonDerBoolean,
+    /// The components of a SET were sorted by their tag or the elements of a SET OF by their encoding
+    UnsortedSet,
+    /// The unused padding bits of a BIT STRING were not zero
+    BitStringPadding,
+}
+
+/// A transformation that was applied to the object at `offset` in the input
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Change {
+    /// The offset of the transformed object within the input
+    pub offset: usize,
+    /// The tag of the transformed object within the input
+    pub tag: u8,
+    /// The applied transformation
+    pub transformation: Transformation,
+}
+
+/// Converts the single BER object in `input` into canonical DER, writes it into `sink` and returns
+/// all transformations in the order they were applied
+///
+/// Since the universal type of a constructed object is only known by its tag, implicitly tagged
+/// strings, booleans and integers are copied as they are. For the same reason, a SET whose
+/// components have distinct tags is sorted by tag class and number like a SET, and any other SET is
+/// sorted by the encodings of its elements like a SET OF.
+///
+/// _Note: A SET OF whose elements have distinct tags (e.g. a SET OF CHOICE) is thus sorted like a
+/// SET, which only differs from the encoding order if primitive and constructed tags are mixed._
+pub fn canonicalize<S: Sink>(input: &[u8], sink: &mut S) -> Result<Vec<Change>, Asn1DerError> {
+    let object = BerObject::decode(input).propagate(e!("Failed to decode BER object"))?;
+    if object.raw().len() != input.len() {
+        Err(einval!("Input contains trailing data after the BER object"))?;
+    }
+
+    // Canonicalize the object and write it
+    let mut canonicalizer = Canonicalizer { input, changes: Vec::new() };
+    let mut der = Vec::new();
+    canonicalizer.object(object, 0, &mut der).propagate(e!("Failed to canonicalize BER object"))?;
+    sink.write_all(&der).propagate(e!("Failed to write DER object"))?;
+    Ok(canonicalizer.changes)
+}
+
+/// A BER-to-DER canonicalizer that records the applied transformations
+struct Canonicalizer<'a> {
+    input: &'a [u8],
+    changes: Vec<Change>,
+}
+impl<'a> Canonicalizer<'a> {
+    /// Records `transformation` for `object`
+    fn report(&mut self, object: BerObject, transformation: Transformation) {
+        // #implicit_validation: `object` is a subslice of `input`, so the offset cannot underflow
+        let offset = (object.raw().as_ptr() as usize).saturating_sub(self.input.as_ptr() as usize);
+        self.changes.push(Change { offset, tag: object.tag(), transformation });
+    }
+
+    /// Canonicalizes `object` and appends it to `der`
+    fn object(&mut self, object: BerObject<'a>, depth: usize, der: &mut Vec<u8>) -> Result<(), Asn1DerError> {
+        // Check the length form
+        match object.is_indefinite() {
+            true => self.report(object, Transformation::IndefiniteLength),
+            false if object.header().len() != minimal_header_len(object.value().len()) => {
+                self.report(object, Transformation::NonMinimalLength)
+            }
+            false => (),
+        }
+
+        // Get the canonical value
+        let (tag, mut value) = match object.tag() {
+            tag if tag & CONSTRUCTED != 0 && is_string(tag & !CONSTRUCTED) => {
+                self.report(object, Transformation::ConstructedString);
+                let mut value = Vec::new();
+                object.copy_value(&mut value).propagate(e!("Failed to reassemble constructed string"))?;
+                (tag & !CONSTRUCTED, value)
+            }
+            tag if tag & CONSTRUCTED != 0 => {
+                let value = self.contents(object, depth).propagate(e!("Failed to canonicalize contents"))?;
+                (tag, value)
+            }
+            tag => (tag, object.value().to_vec()),
+        };
+        match tag {
+            BOOLEAN => self.boolean(object, &mut value).propagate(e!("Failed to canonicalize BOOLEAN"))?,
+            INTEGER | ENUMERATED => self.integer(object, &mut value).propagate(e!("Failed to canonicalize INTEGER"))?,
+            BIT_STRING => self.bit_string(object, &mut value).propagate(e!("Failed to canonicalize BIT STRING"))?,
+            _ => (),
+        }
+
+        // Write the object
+        der.push(tag);
+        length::encode(value.len(), der).propagate(e!("Failed to write length"))?;
+        der.extend_from_slice(&value);
+        Ok(())
+    }
+    /// Canonicalizes the subobjects of the constructed `object`
+    fn contents(&mut self, object: BerObject<'a>, depth: usize) -> Result<Vec<u8>, Asn1DerError> {
+        let depth = match depth {
+            depth if depth < MAX_DEPTH => depth + 1,
+            _ => Err(eunsupported!("The objects are nested too deeply"))?,
+        };
+
+        // Canonicalize each subobject
+        let mut children = Vec::new();
+        for child in object.children() {
+            let child = child.propagate(e!("Failed to decode subobject"))?;
+            let mut der = Vec::new();
+            self.object(child, depth, &mut der).propagate(e!("Failed to canonicalize subobject"))?;
+            children.push(der);
+        }
+
+        if object.tag() == SET {
+            self.sort_set(object, &mut children);
+        }
+        Ok(children.concat())
+    }
+    /// Sorts the canonicalized `children` of the SET or SET OF `object`
+    fn sort_set(&mut self, object: BerObject, children: &mut [Vec<u8>]) {
+        // The tag class and number without the constructed-bit
+        let tag = |der: &Vec<u8>| der.first().map(|tag| tag & !CONSTRUCTED);
+        let mut tags: Vec<_> = children.iter().map(tag).collect();
+        tags.sort_unstable();
+        tags.dedup();
+
+        // DER requires the components of a SET to be sorted by their tag and the elements of a SET OF
+        //  by their encoding
+        match tags.len() == children.len() {
+            true if !children.windows(2).all(|pair| tag(&pair[0]) <= tag(&pair[1])) => {
+                self.report(object, Transformation::UnsortedSet);
+                children.sort_unstable_by_key(tag);
+            }
+            false if !children.windows(2).all(|pair| pair[0] <= pair[1]) => {
+                self.report(object, Transformation::UnsortedSet);
+                children.sort_unstable();
+            }
+            _ => (),
+        }
+    }
+    /// Canonicalizes the BOOLEAN `value`
+    fn boolean(&mut self, object: BerObject, value: &mut [u8]) -> Result<(), Asn1DerError> {
+        match value {
+            [0x00] | [0xff] => Ok(()),
+            [value] => {
+                self.report(object, Transformation::NonDerBoolean);
+                *value = 0xff;
+                Ok(())
+            }
+            _ => Err(einval!("A BOOLEAN must have a length of 1")),
+        }
+    }
+    /// Canonicalizes the INTEGER `value`
+    fn integer(&mut self, object: BerObject, value: &mut Vec<u8>) -> Result<(), Asn1DerError> {
+        // Count the redundant leading bytes
+        let redundant = value
+            .windows(2)
+            .take_while(|pair| {
+                matches!(pair, [0x00, next] if next & 0x80 == 0) || matches!(pair, [0xff, next] if next & 0x80 != 0)
+            })
+            .count();
+        match (value.is_empty(), redundant) {
+            (true, _) => Err(einval!("An INTEGER must not be empty")),
+            (false, 0) => Ok(()),
+            (false, redundant) => {
+                self.report(object, Transformation::NonMinimalInteger);
+                value.drain(..redundant);
+                Ok(())
+            }
+        }
+    }
+    /// Canonicalizes the BIT STRING `value`
+    fn bit_string(&mut self, object: BerObject, value: &mut [u8]) -> Result<(), Asn1DerError> {
+        match value {
+            [0] => Ok(()),
+            [unused_bits, .., last] if *unused_bits <= 7 => {
+                // DER requires the unused bits to be zero
+                let mask = (1u8 << *unused_bits) - 1;
+                if *last & mask != 0 {
+                    self.report(object, Transformation::BitStringPadding);
+                    *last &= !mask;
+                }
+                Ok(())
+            }
+            _ => Err(einval!("Invalid BIT STRING")),
+        }
+    }
+}
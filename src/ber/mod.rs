@@ -0,0 +1,398 @@
+//! A lenient BER decoder that supports indefinite lengths, non-minimal lengths and constructed
+//! strings
+
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+mod canonicalize;
+
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::ber::canonicalize::{canonicalize, Change, Transformation};
+use crate::der::DerObject;
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, Sink, Source};
+
+/// A mod for BER-length-decoding
+pub mod length {
+    use crate::Asn1DerError;
+    use crate::Source;
+
+    /// A BER length
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub enum Length {
+        /// A definite length
+        Definite(usize),
+        /// The indefinite length form (`0x80`); the value is terminated by an end-of-contents marker
+        /// (`00 00`)
+        Indefinite,
+    }
+
+    /// Tries to read the length or returns `None` if there are not enough bytes
+    ///
+    /// _Note: In contrast to DER, BER allows non-minimal lengths; i.e. complex lengths < 128 and
+    /// leading zero bytes are accepted._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    #[cfg_attr(feature = "no_panic", inline(always))]
+    pub fn decode<S: Source>(source: &mut S) -> Result<Option<Length>, Asn1DerError> {
+        // Read first byte
+        let first = match source.read() {
+            Ok(first) => first,
+            Err(_) => return Ok(None),
+        };
+
+        // Check if we have a simple, an indefinite or a complex length
+        match first {
+            len if len < 0b1000_0000 => Ok(Some(Length::Definite(len as usize))),
+            0b1000_0000 => Ok(Some(Length::Indefinite)),
+            0b1111_1111 => Err(einval!("Encountered reserved length byte `0xff`")),
+            size => {
+                // Read the complex length bytes or return `None` in case the length is truncated
+                let mut len = 0usize;
+                for _ in 0..size & 0b0111_1111 {
+                    let next = match source.read() {
+                        Ok(next) => next,
+                        Err(_) => return Ok(None),
+                    };
+                    len = match len.checked_mul(256) {
+                        Some(len) => len | next as usize,
+                        None => Err(eunsupported!("The object length is greater than `usize::max_value()`"))?,
+                    };
+                }
+                Ok(Some(Length::Definite(len)))
+            }
+        }
+    }
+}
+
+use length::Length;
+
+/// The constructed-bit of a tag
+pub(crate) const CONSTRUCTED: u8 = 0b0010_0000;
+/// The tag of a primitive BIT STRING
+pub(crate) const BIT_STRING: u8 = 0x03;
+
+/// Checks whether the primitive `tag` is a string type that can also be encoded in constructed form
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+pub(crate) fn is_string(tag: u8) -> bool {
+    // BIT STRING, OCTET STRING, ObjectDescriptor and the character string and time types
+    matches!(tag, 0x03 | 0x04 | 0x07 | 0x0c | 0x12..=0x1e)
+}
+
+/// Computes the length of a header with a minimal length encoding for a value of `value_len` bytes
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+pub(crate) fn minimal_header_len(value_len: usize) -> usize {
+    match value_len {
+        len if len < 128 => 2,
+        // #implicit_validation: The amount of length bytes cannot exceed the byte length of `usize`
+        len => 2usize.saturating_add((usize::BITS - len.leading_zeros()).div_ceil(8) as usize),
+    }
+}
+
+/// Decodes the header at `&raw[header_start..]` and returns the tag, the value start and the length
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn decode_header(raw: &[u8], header_start: usize) -> Result<(u8, usize, Length), Asn1DerError> {
+    let mut value_start = header_start;
+    let mut iter = raw.iter().skip(header_start).counting_source(&mut value_start);
+
+    // Read tag and length
+    let tag = iter.read().propagate(e!("Failed to read tag"))?;
+    let len = length::decode(&mut iter).propagate(e!("Failed to decode length"))?.ok_or(eio!("Truncated length"))?;
+    Ok((tag, value_start, len))
+}
+
+/// Finds the end-of-contents marker for the indefinite length value at `&raw[value_start..]` and
+/// returns its position
+///
+/// _Note: This does not recurse; nested indefinite length objects are tracked by their depth._
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn find_end_of_contents(raw: &[u8], value_start: usize) -> Result<usize, Asn1DerError> {
+    let (mut pos, mut depth) = (value_start, 1usize);
+    loop {
+        let (tag, next, len) = decode_header(raw, pos).propagate(e!("Failed to decode nested object"))?;
+        match (tag, len) {
+            // An end-of-contents marker closes the innermost indefinite length object
+            (0x00, Length::Definite(0)) => match depth {
+                1 => return Ok(pos),
+                _ => depth = depth.saturating_sub(1),
+            },
+            (0x00, _) => Err(einval!("End-of-contents marker has a non-zero length"))?,
+            (tag, Length::Indefinite) if tag & CONSTRUCTED == 0 => {
+                Err(einval!("Primitive objects must not use the indefinite length form"))?
+            }
+            (_, Length::Indefinite) => match depth.checked_add(1) {
+                Some(next_depth) => depth = next_depth,
+                None => Err(eunsupported!("The nesting depth would exceed `usize::max_value()`"))?,
+            },
+            // Skip the entire definite length object
+            (_, Length::Definite(len)) => match next.checked_add(len) {
+                Some(end) if end <= raw.len() => {
+                    pos = end;
+                    continue;
+                }
+                Some(_) => Err(eio!("The object is truncated"))?,
+                None => Err(eunsupported!("The object bounds would exceed `usize::max_value()`"))?,
+            },
+        }
+        pos = next;
+    }
+}
+
+/// Checks that the definite length value `&raw[value_start..value_end]` does not contain an
+/// end-of-contents marker
+///
+/// _Note: BER only allows end-of-contents markers within indefinite length values; nested objects
+/// are skipped entirely._
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn check_definite_value(raw: &[u8], value_start: usize, value_end: usize) -> Result<(), Asn1DerError> {
+    let raw = raw.get(..value_end).ok_or(eio!("The object is truncated"))?;
+    let mut pos = value_start;
+    while pos < value_end {
+        let object = BerObject::decode_at(raw, pos).propagate(e!("Failed to decode nested object"))?;
+        if object.tag == 0x00 {
+            Err(einval!("End-of-contents marker within a definite length value"))?;
+        }
+        // #implicit_validation: The object has been decoded from `raw`, so its end cannot exceed
+        // `usize::max_value()`
+        pos = pos.saturating_add(object.raw.len());
+    }
+    Ok(())
+}
+
+/// An untyped BER object
+///
+/// In contrast to `DerObject`, this supports indefinite lengths, non-minimal lengths and
+/// constructed strings. The `value` of an indefinite length object does not contain the
+/// end-of-contents marker, but `raw` does.
+#[derive(Copy, Clone)]
+pub struct BerObject<'a> {
+    raw: &'a [u8],
+    header: &'a [u8],
+    tag: u8,
+    value: &'a [u8],
+    indefinite: bool,
+}
+impl<'a> BerObject<'a> {
+    /// Decodes an object from `raw`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode(raw: &'a [u8]) -> Result<Self, Asn1DerError> {
+        Self::decode_at(raw, 0)
+    }
+    /// Decodes an object from `&raw[header_start..]`
+    #[doc(hidden)]
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_at(raw: &'a [u8], header_start: usize) -> Result<Self, Asn1DerError> {
+        let (tag, value_start, len) = decode_header(raw, header_start).propagate(e!("Failed to decode header"))?;
+
+        // Compute the value end and the object end (which includes the end-of-contents marker)
+        let (value_end, end) = match len {
+            Length::Definite(len) => match value_start.checked_add(len) {
+                Some(value_end) => (value_end, value_end),
+                None => Err(eunsupported!("The object bounds would exceed `usize::max_value()`"))?,
+            },
+            Length::Indefinite if tag & CONSTRUCTED == 0 => {
+                Err(einval!("Primitive objects must not use the indefinite length form"))?
+            }
+            Length::Indefinite => {
+                let value_end =
+                    find_end_of_contents(raw, value_start).propagate(e!("Failed to find end-of-contents marker"))?;
+                // #implicit_validation: The end-of-contents marker has been decoded from `raw`, so
+                // its end cannot exceed `usize::max_value()`
+                (value_end, value_end.saturating_add(2))
+            }
+        };
+
+        // Get the slices
+        let header = raw.get(header_start..value_start).ok_or(eio!("The object is truncated"))?;
+        let value = raw.get(value_start..value_end).ok_or(eio!("The object is truncated"))?;
+        let raw = raw.get(header_start..end).ok_or(eio!("The object is truncated"))?;
+        Ok(Self { raw, header, tag, value, indefinite: len == Length::Indefinite })
+    }
+
+    /// The underlying raw slice
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn raw(self) -> &'a [u8] {
+        self.raw
+    }
+    /// The object header
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn header(self) -> &'a [u8] {
+        self.header
+    }
+    /// The object tag
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn tag(self) -> u8 {
+        self.tag
+    }
+    /// The object value (without the end-of-contents marker)
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn value(self) -> &'a [u8] {
+        self.value
+    }
+    /// Whether the object is constructed
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn is_constructed(self) -> bool {
+        self.tag & CONSTRUCTED != 0
+    }
+    /// Whether the object uses the indefinite length form
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn is_indefinite(self) -> bool {
+        self.indefinite
+    }
+
+    /// An iterator over the subobjects of a constructed object
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn children(self) -> Children<'a> {
+        Children { value: self.value, pos: 0 }
+    }
+    /// An allocation-free iterator over the segments of a string
+    ///
+    /// A primitive object yields its value as single segment; a constructed string yields the
+    /// values of its (possibly nested) primitive segments.
+    ///
+    /// _Note: Each segment of a BIT STRING starts with its own unused-bits byte; use `copy_value` to
+    /// reassemble a BIT STRING._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn segments(self) -> Result<Segments<'a>, Asn1DerError> {
+        let tag = self.tag & !CONSTRUCTED;
+        match (self.is_constructed(), is_string(tag)) {
+            (false, _) => Ok(Segments { value: &[], pos: 0, tag, single: Some(self.value) }),
+            (true, true) => {
+                if !self.indefinite {
+                    check_definite_value(self.value, 0, self.value.len())
+                        .propagate(e!("Invalid constructed string"))?;
+                }
+                Ok(Segments { value: self.value, pos: 0, tag, single: None })
+            }
+            (true, false) => Err(einval!("Only constructed strings consist of segments")),
+        }
+    }
+    /// Writes the reassembled value of a (possibly constructed) string into `sink`
+    ///
+    /// _Note: For a BIT STRING, this writes a single unused-bits byte followed by the bits of all
+    /// segments; only the last segment may have unused bits._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn copy_value<S: Sink>(self, sink: &mut S) -> Result<(), Asn1DerError> {
+        let mut segments = self.segments().propagate(e!("Failed to iterate over segments"))?;
+        match self.tag & !CONSTRUCTED {
+            BIT_STRING => {
+                // Validate the segments and get the unused bits of the last segment
+                let mut unused_bits = 0;
+                for segment in segments.by_ref() {
+                    if unused_bits != 0 {
+                        Err(einval!("Only the last segment of a BIT STRING may have unused bits"))?;
+                    }
+                    unused_bits = match segment.propagate(e!("Failed to read segment"))? {
+                        [0] => 0,
+                        [unused_bits, _, ..] if *unused_bits <= 7 => *unused_bits,
+                        _ => Err(einval!("Invalid BIT STRING segment"))?,
+                    };
+                }
+
+                // Write the unused bits and the bits of all segments
+                sink.write(unused_bits).propagate(e!("Failed to write unused bits"))?;
+                let mut segments = self.segments().propagate(e!("Failed to iterate over segments"))?;
+                segments.try_for_each(|segment| {
+                    let segment = segment.propagate(e!("Failed to read segment"))?;
+                    sink.write_all(segment.get(1..).unwrap_or_default()).propagate(e!("Failed to write value"))
+                })
+            }
+            _ => segments.try_for_each(|segment| {
+                let segment = segment.propagate(e!("Failed to read segment"))?;
+                sink.write_all(segment).propagate(e!("Failed to write value"))
+            }),
+        }
+    }
+}
+impl<'a> From<DerObject<'a>> for BerObject<'a> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn from(object: DerObject<'a>) -> Self {
+        Self { raw: object.raw(), header: object.header(), tag: object.tag(), value: object.value(), indefinite: false }
+    }
+}
+
+/// An iterator over the subobjects of a constructed BER object
+pub struct Children<'a> {
+    value: &'a [u8],
+    pos: usize,
+}
+impl<'a> Iterator for Children<'a> {
+    type Item = Result<BerObject<'a>, Asn1DerError>;
+
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.value.len() {
+            return None;
+        }
+
+        // Decode the next subobject or stop on error
+        match BerObject::decode_at(self.value, self.pos) {
+            Ok(object) => {
+                self.pos = self.pos.saturating_add(object.raw.len());
+                Some(Ok(object))
+            }
+            Err(e) => {
+                self.pos = self.value.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// An allocation-free iterator over the primitive segments of a BER string
+pub struct Segments<'a> {
+    value: &'a [u8],
+    pos: usize,
+    tag: u8,
+    single: Option<&'a [u8]>,
+}
+impl<'a> Segments<'a> {
+    /// Reads the next segment
+    ///
+    /// _Note: Since segments are stored contiguously, nested constructed segments are flattened by
+    /// entering them instead of recursing into them._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_segment(&mut self) -> Result<Option<&'a [u8]>, Asn1DerError> {
+        while self.pos < self.value.len() {
+            let (tag, value_start, len) =
+                decode_header(self.value, self.pos).propagate(e!("Failed to decode segment header"))?;
+            match (tag, len) {
+                // Skip the end-of-contents marker of a nested indefinite length segment (markers
+                //  within definite length segments are rejected when entering them)
+                (0x00, Length::Definite(0)) => self.pos = value_start,
+                (tag, Length::Definite(len)) if tag == self.tag => {
+                    let value_end = value_start.checked_add(len).ok_or(eio!("The segment is truncated"))?;
+                    let segment = self.value.get(value_start..value_end).ok_or(eio!("The segment is truncated"))?;
+                    self.pos = value_end;
+                    return Ok(Some(segment));
+                }
+                // Enter the nested constructed segment
+                (tag, Length::Definite(len)) if tag == self.tag | CONSTRUCTED => {
+                    let value_end = value_start.checked_add(len).ok_or(eio!("The segment is truncated"))?;
+                    check_definite_value(self.value, value_start, value_end)
+                        .propagate(e!("Invalid constructed segment"))?;
+                    self.pos = value_start;
+                }
+                (tag, Length::Indefinite) if tag == self.tag | CONSTRUCTED => self.pos = value_start,
+                _ => Err(einval!("Invalid segment in constructed string"))?,
+            }
+        }
+        Ok(None)
+    }
+}
+impl<'a> Iterator for Segments<'a> {
+    type Item = Result<&'a [u8], Asn1DerError>;
+
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(single) = self.single.take() {
+            return Some(Ok(single));
+        }
+
+        // Read the next segment or stop on error
+        match self.read_segment() {
+            Ok(segment) => segment.map(Ok),
+            Err(e) => {
+                self.pos = self.value.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! CER (Canonical Encoding Rules) output and validation
+//!
+//! CER differs from DER in that constructed objects always use the indefinite length form and that
+//! strings with more than 1000 contents octets are split into 1000-octet segments.
+
+use crate::ber::{is_string, minimal_header_len, BerObject, BIT_STRING, CONSTRUCTED};
+use crate::der::length;
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, Sink};
+
+/// The maximum amount of contents octets of a primitive string or string segment
+pub const SEGMENT_LEN: usize = 1000;
+/// The maximum nesting depth of constructed objects
+pub const MAX_DEPTH: usize = 64;
+
+/// The tag of a BOOLEAN
+const BOOLEAN: u8 = 0x01;
+/// The tag of an INTEGER
+const INTEGER: u8 = 0x02;
+/// The tag of an ENUMERATED
+const ENUMERATED: u8 = 0x0a;
+/// The tag of a SET or SET OF
+const SET: u8 = 0x31;
+
+/// The elements of an open constructed object within a `CerSink`
+#[derive(Copy, Clone)]
+enum Elements {
+    /// The object is not a SET or SET OF
+    Any,
+    /// The object is a SET or SET OF without elements
+    SetEmpty,
+    /// The object is a SET or SET OF whose elements are encoded equally in DER and CER
+    SetUnchanged,
+    /// The object is a SET or SET OF with a single element that is re-encoded in CER
+    SetChanged,
+}
+
+/// The state of a `CerSink`
+#[derive(Copy, Clone)]
+enum State {
+    /// The next element is a tag
+    Tag,
+    /// The next element is the first length byte
+    Length { tag: u8 },
+    /// The next elements are `remaining` complex length bytes
+    ComplexLength { tag: u8, len: usize, remaining: usize },
+    /// The next `remaining` elements are copied as they are
+    Primitive { remaining: usize },
+    /// The next `remaining` elements are split into segments
+    Segmented { tag: u8, remaining: usize, segment: usize, unused_bits: Option<u8> },
+}
+
+/// A sink that converts the DER objects written to it into CER and writes them into the
+/// underlying sink
+///
+/// Since DER and CER only differ in the length form of constructed objects and the segmentation of
+/// long strings, this works on the fly without any allocation; nested objects are tracked up to a
+/// depth of [`MAX_DEPTH`].
+///
+/// _Note: Since CER sorts the elements of a SET OF by their CER encodings, which cannot be done on
+/// the fly, a SET or SET OF with more than one element must not contain constructed elements or
+/// strings with more than 1000 contents octets. Strings are recognized by their universal tag,
+/// so implicitly tagged strings with more than 1000 contents octets are not segmented._
+pub struct CerSink<S: Sink> {
+    sink: S,
+    /// The amount of DER bytes written
+    pos: usize,
+    /// The end positions of the open constructed objects
+    ends: [usize; MAX_DEPTH],
+    /// The elements of the open constructed objects
+    elements: [Elements; MAX_DEPTH],
+    depth: usize,
+    state: State,
+}
+impl<S: Sink> CerSink<S> {
+    /// Creates a new CER sink that writes into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(sink: S) -> Self {
+        Self { sink, pos: 0, ends: [0; MAX_DEPTH], elements: [Elements::Any; MAX_DEPTH], depth: 0, state: State::Tag }
+    }
+    /// Ensures that all written objects are complete and returns the underlying sink
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn finish(self) -> Result<S, Asn1DerError> {
+        match (self.state, self.depth) {
+            (State::Tag, 0) => Ok(self.sink),
+            _ => Err(eio!("The DER object is truncated")),
+        }
+    }
+
+    /// Writes the CER header for the DER object with `tag` and `len`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn header(&mut self, tag: u8, len: usize) -> Result<(), Asn1DerError> {
+        // Validate the object bounds
+        let end = match self.pos.checked_add(len) {
+            Some(end) => end,
+            None => Err(eunsupported!("The object bounds would exceed `usize::max_value()`"))?,
+        };
+        if let Some(parent_end) = self.depth.checked_sub(1).and_then(|i| self.ends.get(i)) {
+            if end > *parent_end {
+                Err(einval!("The object exceeds its parent object"))?;
+            }
+        }
+
+        // The DER order of the elements of a SET OF is only retained if they are encoded equally in CER
+        let reencoded = tag & CONSTRUCTED != 0 || (is_string(tag) && len > SEGMENT_LEN);
+        if let Some(elements) = self.depth.checked_sub(1).and_then(|i| self.elements.get_mut(i)) {
+            *elements = match (*elements, reencoded) {
+                (Elements::Any, _) => Elements::Any,
+                (Elements::SetEmpty, true) => Elements::SetChanged,
+                (Elements::SetEmpty | Elements::SetUnchanged, false) => Elements::SetUnchanged,
+                (Elements::SetUnchanged, true) | (Elements::SetChanged, _) => Err(eunsupported!(
+                    "A SET with more than one element must not contain constructed elements or long strings"
+                ))?,
+            };
+        }
+
+        match tag {
+            // Constructed objects use the indefinite length form
+            tag if tag & CONSTRUCTED != 0 => {
+                *self.ends.get_mut(self.depth).ok_or(eunsupported!("The objects are nested too deeply"))? = end;
+                *self.elements.get_mut(self.depth).ok_or(eunsupported!("The objects are nested too deeply"))? =
+                    match tag {
+                        SET => Elements::SetEmpty,
+                        _ => Elements::Any,
+                    };
+                self.depth = self.depth.saturating_add(1);
+                self.sink.write(tag).propagate(e!("Failed to write tag"))?;
+                self.sink.write(0x80).propagate(e!("Failed to write indefinite length"))?;
+                self.state = State::Tag;
+            }
+            // Long strings are split into segments
+            tag if is_string(tag) && len > SEGMENT_LEN => {
+                self.sink.write(tag | CONSTRUCTED).propagate(e!("Failed to write tag"))?;
+                self.sink.write(0x80).propagate(e!("Failed to write indefinite length"))?;
+                self.state = State::Segmented { tag, remaining: len, segment: 0, unused_bits: None };
+            }
+            tag => {
+                self.sink.write(tag).propagate(e!("Failed to write tag"))?;
+                length::encode(len, &mut self.sink).propagate(e!("Failed to write length"))?;
+                self.state = State::Primitive { remaining: len };
+            }
+        }
+        self.close().propagate(e!("Failed to close constructed objects"))
+    }
+    /// Copies the next contents octet of a segmented string
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn segmented(
+        &mut self,
+        tag: u8,
+        remaining: usize,
+        segment: usize,
+        unused_bits: Option<u8>,
+        e: u8,
+    ) -> Result<(), Asn1DerError> {
+        // Each BIT STRING segment starts with its own unused-bits byte
+        if let (BIT_STRING, None) = (tag, unused_bits) {
+            let remaining = remaining.saturating_sub(1);
+            self.state = State::Segmented { tag, remaining, segment, unused_bits: Some(e) };
+            return Ok(());
+        }
+
+        // Start a new segment if necessary
+        let segment = match (segment, unused_bits) {
+            (0, Some(unused_bits)) => {
+                let len = remaining.min(SEGMENT_LEN - 1);
+                self.sink.write(tag).propagate(e!("Failed to write segment tag"))?;
+                length::encode(len.saturating_add(1), &mut self.sink)
+                    .propagate(e!("Failed to write segment length"))?;
+                let unused_bits = if len == remaining { unused_bits } else { 0 };
+                self.sink.write(unused_bits).propagate(e!("Failed to write unused bits"))?;
+                len
+            }
+            (0, None) => {
+                let len = remaining.min(SEGMENT_LEN);
+                self.sink.write(tag).propagate(e!("Failed to write segment tag"))?;
+                length::encode(len, &mut self.sink).propagate(e!("Failed to write segment length"))?;
+                len
+            }
+            (segment, _) => segment,
+        };
+
+        // Copy the element and close the string after the last element
+        self.sink.write(e).propagate(e!("Failed to write segment"))?;
+        let (remaining, segment) = (remaining.saturating_sub(1), segment.saturating_sub(1));
+        match remaining {
+            0 => {
+                self.write_end_of_contents().propagate(e!("Failed to write end-of-contents"))?;
+                self.state = State::Tag;
+                self.close().propagate(e!("Failed to close constructed objects"))
+            }
+            _ => {
+                self.state = State::Segmented { tag, remaining, segment, unused_bits };
+                Ok(())
+            }
+        }
+    }
+    /// Writes the end-of-contents markers for all completed constructed objects
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn close(&mut self) -> Result<(), Asn1DerError> {
+        // Complete empty primitive objects
+        if let State::Primitive { remaining: 0 } = self.state {
+            self.state = State::Tag;
+        }
+
+        // Close all constructed objects that end at the current position
+        while let State::Tag = self.state {
+            match self.depth.checked_sub(1) {
+                Some(top) if self.ends.get(top) == Some(&self.pos) => {
+                    self.write_end_of_contents().propagate(e!("Failed to write end-of-contents"))?;
+                    self.depth = top;
+                }
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+    /// Writes an end-of-contents marker
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_end_of_contents(&mut self) -> Result<(), Asn1DerError> {
+        self.sink.write(0x00).propagate(e!("Failed to write end-of-contents"))?;
+        self.sink.write(0x00).propagate(e!("Failed to write end-of-contents"))
+    }
+}
+impl<S: Sink> Sink for CerSink<S> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        self.pos = match self.pos.checked_add(1) {
+            Some(pos) => pos,
+            None => Err(eio!("Cannot write more because the position counter would overflow"))?,
+        };
+
+        match self.state {
+            State::Tag => {
+                self.state = State::Length { tag: e };
+                Ok(())
+            }
+            State::Length { tag } => match e {
+                len if len < 0b1000_0000 => self.header(tag, len as usize),
+                0b1000_0000 => Err(einval!("DER objects must not use the indefinite length form")),
+                size => {
+                    let remaining = (size & 0b0111_1111) as usize;
+                    self.state = State::ComplexLength { tag, len: 0, remaining };
+                    Ok(())
+                }
+            },
+            State::ComplexLength { tag, len, remaining } => {
+                let len = match len.checked_mul(256) {
+                    Some(len) => len | e as usize,
+                    None => Err(eunsupported!("The object length is greater than `usize::max_value()`"))?,
+                };
+                match remaining.saturating_sub(1) {
+                    0 => self.header(tag, len),
+                    remaining => {
+                        self.state = State::ComplexLength { tag, len, remaining };
+                        Ok(())
+                    }
+                }
+            }
+            State::Primitive { remaining } => {
+                self.sink.write(e).propagate(e!("Failed to write value"))?;
+                self.state = State::Primitive { remaining: remaining.saturating_sub(1) };
+                self.close().propagate(e!("Failed to close constructed objects"))
+            }
+            State::Segmented { tag, remaining, segment, unused_bits } => {
+                self.segmented(tag, remaining, segment, unused_bits, e)
+            }
+        }
+    }
+}
+
+/// Validates that `input` is a single object in canonical CER form
+///
+/// _Note: Strings are recognized by their universal tag, so implicitly tagged strings are not
+/// required to be segmented._
+///
+/// _Note: Since nested objects are validated recursively, this function is not attributed with
+/// `no_panic`; the nesting depth is limited to [`MAX_DEPTH`]._
+pub fn validate(input: &[u8]) -> Result<(), Asn1DerError> {
+    let object = BerObject::decode(input).propagate(e!("Failed to decode object"))?;
+    if object.raw().len() != input.len() {
+        Err(einval!("Input contains trailing data after the object"))?;
+    }
+    validate_object(object, 0).propagate(e!("Invalid CER object"))
+}
+/// Validates `object` at nesting `depth`
+fn validate_object(object: BerObject, depth: usize) -> Result<(), Asn1DerError> {
+    let tag = object.tag();
+    match (object.is_constructed(), object.is_indefinite()) {
+        (true, false) => Err(einval!("Constructed objects must use the indefinite length form")),
+        (true, true) if is_string(tag & !CONSTRUCTED) => validate_segments(object),
+        (true, true) => {
+            let depth = match depth {
+                depth if depth < MAX_DEPTH => depth + 1,
+                _ => Err(eunsupported!("The objects are nested too deeply"))?,
+            };
+
+            // Validate the subobjects and the order of the elements of a SET OF
+            let mut previous: Option<&[u8]> = None;
+            for child in object.children() {
+                let child = child.propagate(e!("Failed to decode subobject"))?;
+                validate_object(child, depth).propagate(e!("Invalid subobject"))?;
+                if tag == SET && matches!(previous, Some(previous) if previous > child.raw()) {
+                    Err(einval!("The elements of a SET OF are not sorted"))?;
+                }
+                previous = Some(child.raw());
+            }
+            Ok(())
+        }
+        (false, _) => {
+            validate_length(object).propagate(e!("Invalid length"))?;
+            if is_string(tag) && object.value().len() > SEGMENT_LEN {
+                Err(einval!("Strings with more than 1000 contents octets must be segmented"))?;
+            }
+            validate_value(tag, object.value())
+        }
+    }
+}
+/// Validates the segments of a constructed string
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn validate_segments(object: BerObject) -> Result<(), Asn1DerError> {
+    let tag = object.tag() & !CONSTRUCTED;
+    let (mut previous, mut len): (Option<&[u8]>, usize) = (None, 0);
+    for segment in object.children() {
+        let segment = segment.propagate(e!("Failed to decode segment"))?;
+        if segment.tag() != tag {
+            Err(einval!("Segments must be primitive and of the same type as the string"))?;
+        }
+        validate_length(segment).propagate(e!("Invalid segment length"))?;
+
+        // All segments except the last must have 1000 contents octets
+        match previous {
+            Some(previous) if previous.len() != SEGMENT_LEN => {
+                Err(einval!("Only the last segment may have less than 1000 contents octets"))?
+            }
+            Some([unused_bits, ..]) if tag == BIT_STRING && *unused_bits != 0 => {
+                Err(einval!("Only the last segment of a BIT STRING may have unused bits"))?
+            }
+            _ => (),
+        }
+        previous = Some(segment.value());
+
+        // The primitive encoding of a BIT STRING has a single unused-bits byte instead of one per segment
+        let data = match tag {
+            BIT_STRING => segment.value().len().saturating_sub(1),
+            _ => segment.value().len(),
+        };
+        len = len.saturating_add(data);
+    }
+    if tag == BIT_STRING {
+        len = len.saturating_add(1);
+    }
+
+    // Validate the last segment
+    match previous {
+        _ if len <= SEGMENT_LEN => Err(einval!("Strings with at most 1000 contents octets must be primitive")),
+        Some([]) => Err(einval!("The last segment must not be empty")),
+        Some([_]) if tag == BIT_STRING => Err(einval!("The last segment of a BIT STRING must not be empty")),
+        Some(last) => validate_value(tag, last),
+        None => Err(einval!("Strings with at most 1000 contents octets must be primitive")),
+    }
+}
+/// Validates that the length of the primitive `object` is encoded in its minimal form
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn validate_length(object: BerObject) -> Result<(), Asn1DerError> {
+    match object.is_indefinite() {
+        true => Err(einval!("Primitive objects must use the definite length form")),
+        false if object.header().len() != minimal_header_len(object.value().len()) => {
+            Err(einval!("The length uses more bytes than necessary"))
+        }
+        false => Ok(()),
+    }
+}
+/// Validates the primitive `value` of type `tag`
+#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+fn validate_value(tag: u8, value: &[u8]) -> Result<(), Asn1DerError> {
+    match (tag, value) {
+        (BOOLEAN, [0x00] | [0xff]) => Ok(()),
+        (BOOLEAN, _) => Err(einval!("A BOOLEAN must be encoded as `0x00` or `0xff`")),
+        (INTEGER | ENUMERATED, []) => Err(einval!("An INTEGER must not be empty")),
+        (INTEGER | ENUMERATED, [0x00, next, ..]) if next & 0x80 == 0 => Err(einval!("Non-minimal INTEGER")),
+        (INTEGER | ENUMERATED, [0xff, next, ..]) if next & 0x80 != 0 => Err(einval!("Non-minimal INTEGER")),
+        (BIT_STRING, [0]) => Ok(()),
+        (BIT_STRING, [unused_bits, .., last]) if *unused_bits <= 7 => match last & ((1u8 << *unused_bits) - 1) {
+            0 => Ok(()),
+            _ => Err(einval!("The unused bits of a BIT STRING must be zero")),
+        },
+        (BIT_STRING, _) => Err(einval!("Invalid BIT STRING")),
+        _ => Ok(()),
+    }
+}
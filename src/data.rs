@@ -1,11 +1,29 @@
 use crate::error::ErrorChain;
 use crate::Asn1DerError;
-use core::{iter, slice};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use alloc::vec::Vec;
+use core::{iter, mem, slice};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+/// The size of the stack buffer that is used to copy elements in chunks
+pub(crate) const CHUNK_SIZE: usize = 256;
 
 /// A trait defining a byte source
 pub trait Source: Sized {
     /// Reads the next element
     fn read(&mut self) -> Result<u8, Asn1DerError>;
+    /// Fills `buf` with the next elements
+    ///
+    /// _Note: The default implementation reads each element separately; sources that are backed by
+    /// memory override this with a bulk copy._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        buf.iter_mut().try_for_each(|b| {
+            *b = self.read()?;
+            Ok(())
+        })
+    }
 
     /// Creates a counting source
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -23,6 +41,10 @@ impl<S: Source> Source for &mut S {
     fn read(&mut self) -> Result<u8, Asn1DerError> {
         (*self).read()
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        (*self).read_exact(buf)
+    }
 }
 impl<'a> Source for slice::Iter<'a, u8> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -32,6 +54,18 @@ impl<'a> Source for slice::Iter<'a, u8> {
             None => Err(eio!("Cannot read beyond end of slice-iterator")),
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        let slice = self.as_slice();
+        match (slice.get(..buf.len()), slice.get(buf.len()..)) {
+            (Some(head), Some(rest)) => {
+                buf.copy_from_slice(head);
+                *self = rest.iter();
+                Ok(())
+            }
+            _ => Err(eio!("Cannot read beyond end of slice-iterator")),
+        }
+    }
 }
 impl<'a, A: Iterator<Item = &'a u8>, B: Iterator<Item = &'a u8>> Source for iter::Chain<A, B> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -73,6 +107,17 @@ impl<'a, S: Source> Source for CountingSource<'a, S> {
             _ => Err(eio!("Cannot read more because the position counter would overflow")),
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        self.source.read_exact(buf).propagate(e!("Failed to read elements from underlying source"))?;
+        match self.ctr.checked_add(buf.len()) {
+            Some(ctr_next) => {
+                *self.ctr = ctr_next;
+                Ok(())
+            }
+            _ => Err(eio!("Cannot read more because the position counter would overflow")),
+        }
+    }
 }
 
 /// A source that also copies each read element to the `sink`
@@ -92,8 +137,15 @@ impl<S: Source, U: Sink> CopyingSource<S, U> {
     }
     /// Copies the next `n` elements
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
-    pub fn copy_n(&mut self, n: usize) -> Result<(), Asn1DerError> {
-        (0..n).try_for_each(|_| self.copy_next())
+    pub fn copy_n(&mut self, mut n: usize) -> Result<(), Asn1DerError> {
+        // Copy the elements in chunks via a stack buffer
+        let mut buf = [0; CHUNK_SIZE];
+        while n > 0 {
+            let chunk = buf.get_mut(..n.min(CHUNK_SIZE)).unwrap_or_default();
+            self.read_exact(chunk)?;
+            n = n.saturating_sub(chunk.len());
+        }
+        Ok(())
     }
 }
 impl<S: Source, U: Sink> Source for CopyingSource<S, U> {
@@ -103,12 +155,25 @@ impl<S: Source, U: Sink> Source for CopyingSource<S, U> {
         self.sink.write(e).propagate(e!("Failed to copy element to sink"))?;
         Ok(e)
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        self.source.read_exact(buf).propagate(e!("Failed to read elements from underlying source"))?;
+        self.sink.write_all(buf).propagate(e!("Failed to copy elements to sink"))
+    }
 }
 
 /// A trait defining a byte sink
 pub trait Sink: Sized {
     /// Writes `e` to `self`
     fn write(&mut self, e: u8) -> Result<(), Asn1DerError>;
+    /// Writes all `bytes` to `self`
+    ///
+    /// _Note: The default implementation writes each element separately; sinks that are backed by
+    /// memory override this with a bulk copy._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        bytes.iter().try_for_each(|b| self.write(*b))
+    }
     /// Creates a counting sink
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn counting_sink(self, ctr: &mut usize) -> CountingSink<'_, Self> {
@@ -120,6 +185,10 @@ impl<S: Sink> Sink for &mut S {
     fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
         (*self).write(e)
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        (*self).write_all(bytes)
+    }
 }
 impl<'a> Sink for slice::IterMut<'a, u8> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -132,13 +201,45 @@ impl<'a> Sink for slice::IterMut<'a, u8> {
             None => Err(eio!("Cannot write beyond end of slice-iterator")),
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        let slice = mem::take(self).into_slice();
+        match slice.len() >= bytes.len() {
+            true => {
+                // #implicit_validation: Since we have checked the length, the split is always at `bytes.len()`
+                let (head, rest) = slice.split_at_mut(bytes.len().min(slice.len()));
+                head.copy_from_slice(bytes);
+                *self = rest.iter_mut();
+                Ok(())
+            }
+            false => {
+                *self = slice.iter_mut();
+                Err(eio!("Cannot write beyond end of slice-iterator"))
+            }
+        }
+    }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl Sink for Vec<u8> {
     fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
         self.push(e);
         Ok(())
     }
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+#[cfg(all(feature = "bytes", not(feature = "no_panic")))]
+impl Sink for bytes::BytesMut {
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        bytes::BufMut::put_u8(self, e);
+        Ok(())
+    }
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
 }
 
 /// A sink that counts the amount of elements written
@@ -159,6 +260,27 @@ impl<'a, S: Sink> Sink for CountingSink<'a, S> {
         };
         Ok(())
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.sink.write_all(bytes).propagate(e!("Failed to write elements to underlying source"))?;
+        *self.ctr = match self.ctr.checked_add(bytes.len()) {
+            Some(ctr_next) => ctr_next,
+            None => Err(eio!("Cannot write more because the position counter would overflow"))?,
+        };
+        Ok(())
+    }
+}
+
+/// A sink that writes into a contiguous buffer and thus allows to patch already written bytes
+pub trait PatchSink: Sink {
+    /// The bytes that have been written into the underlying buffer so far
+    fn written_mut(&mut self) -> &mut [u8];
+}
+impl<S: PatchSink> PatchSink for &mut S {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn written_mut(&mut self) -> &mut [u8] {
+        (*self).written_mut()
+    }
 }
 
 /// A slice-backed sink
@@ -188,6 +310,30 @@ impl<'a> Sink for SliceSink<'a> {
             None => Err(eio!("Cannot write beyond the end-of-slice"))?,
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        let end = match self.pos.checked_add(bytes.len()) {
+            Some(end) => end,
+            None => Err(eio!("Cannot write more because the position counter would overflow"))?,
+        };
+        match self.slice.get_mut(*self.pos..end) {
+            Some(target) => {
+                target.copy_from_slice(bytes);
+                *self.pos = end;
+                Ok(())
+            }
+            None => Err(eio!("Cannot write beyond the end-of-slice"))?,
+        }
+    }
+}
+impl<'a> PatchSink for SliceSink<'a> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn written_mut(&mut self) -> &mut [u8] {
+        // #implicit_validation: `pos` never exceeds the slice length unless it has been modified
+        // externally
+        let pos = (*self.pos).min(self.slice.len());
+        self.slice.get_mut(..pos).unwrap_or_default()
+    }
 }
 impl<'a> From<SliceSink<'a>> for &'a [u8] {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -199,19 +345,221 @@ impl<'a> From<SliceSink<'a>> for &'a [u8] {
     }
 }
 
+/// A trait defining a byte sink that is filled from back to front
+pub trait ReverseSink: Sized {
+    /// Reserves `len` bytes in front of the already written bytes and returns them
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], Asn1DerError>;
+    /// The bytes that have been written so far
+    fn written_mut(&mut self) -> &mut [u8];
+
+    /// Prepends `bytes` to the already written bytes
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn prepend(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        let reserved = self.reserve(bytes.len()).propagate(e!("Failed to reserve bytes"))?;
+        reserved.iter_mut().zip(bytes.iter()).for_each(|(t, b)| *t = *b);
+        Ok(())
+    }
+}
+impl<S: ReverseSink> ReverseSink for &mut S {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], Asn1DerError> {
+        (*self).reserve(len)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn written_mut(&mut self) -> &mut [u8] {
+        (*self).written_mut()
+    }
+}
+
+/// A slice-backed sink that is filled from back to front
+pub struct ReverseSliceSink<'a> {
+    slice: &'a mut [u8],
+    start: usize,
+}
+impl<'a> ReverseSliceSink<'a> {
+    /// Creates a new `ReverseSliceSink` that fills `slice` from its end
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(slice: &'a mut [u8]) -> Self {
+        let start = slice.len();
+        Self { slice, start }
+    }
+}
+impl<'a> ReverseSink for ReverseSliceSink<'a> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], Asn1DerError> {
+        let start = match self.start.checked_sub(len) {
+            Some(start) => start,
+            None => Err(eio!("Cannot write beyond the start-of-slice"))?,
+        };
+        match self.slice.get_mut(start..self.start) {
+            Some(reserved) => {
+                self.start = start;
+                Ok(reserved)
+            }
+            None => Err(eio!("Cannot write beyond the start-of-slice")),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn written_mut(&mut self) -> &mut [u8] {
+        self.slice.get_mut(self.start..).unwrap_or_default()
+    }
+}
+impl<'a> From<ReverseSliceSink<'a>> for &'a [u8] {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn from(sink: ReverseSliceSink<'a>) -> Self {
+        sink.slice.get(sink.start..).unwrap_or_default()
+    }
+}
+
 /// A newtype wrapper around a `&'a mut Vec<u8>` that implements `Sink` and `Into<&'a [u8]>`
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 pub struct VecBacking<'a>(pub &'a mut Vec<u8>);
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<'a> Sink for VecBacking<'a> {
     fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
         self.0.push(e);
         Ok(())
     }
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> PatchSink for VecBacking<'a> {
+    fn written_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<'a> From<VecBacking<'a>> for &'a [u8] {
     fn from(backing: VecBacking<'a>) -> &'a [u8] {
         backing.0.as_slice()
     }
 }
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> Sink for heapless::Vec<u8, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        self.push(e).map_err(|_| eio!("Cannot write beyond the capacity of the vector"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.extend_from_slice(bytes).map_err(|_| eio!("Cannot write beyond the capacity of the vector"))
+    }
+}
+
+/// A newtype wrapper around a `&'a mut heapless::Vec<u8, N>` that implements `Sink` and
+/// `Into<&'a [u8]>`
+#[cfg(feature = "heapless")]
+pub struct HeaplessBacking<'a, const N: usize>(pub &'a mut heapless::Vec<u8, N>);
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> Sink for HeaplessBacking<'a, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        self.0.write(e)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.0.write_all(bytes)
+    }
+}
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> PatchSink for HeaplessBacking<'a, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn written_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> From<HeaplessBacking<'a, N>> for &'a [u8] {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn from(backing: HeaplessBacking<'a, N>) -> &'a [u8] {
+        backing.0.as_slice()
+    }
+}
+
+/// A `Source` adapter for `std::io::Read` that keeps the underlying I/O error
+///
+/// _Note: Single elements are read with a call to the reader each; wrap unbuffered readers into a
+/// `std::io::BufReader`._
+#[cfg(feature = "std")]
+pub struct IoSource<R: Read> {
+    reader: R,
+    error: Option<io::Error>,
+}
+#[cfg(feature = "std")]
+impl<R: Read> IoSource<R> {
+    /// Creates a new I/O source over `reader`
+    pub fn new(reader: R) -> Self {
+        Self { reader, error: None }
+    }
+    /// Takes the last I/O error that occurred while reading
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+    /// Converts `result` into an `io::Result`, preferring the last I/O error over `Asn1DerError`
+    pub fn io_result<T>(&mut self, result: Result<T, Asn1DerError>) -> io::Result<T> {
+        result.map_err(|e| self.error.take().unwrap_or_else(|| e.into()))
+    }
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+#[cfg(feature = "std")]
+impl<R: Read> Source for IoSource<R> {
+    fn read(&mut self) -> Result<u8, Asn1DerError> {
+        let mut buf = [0];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        self.reader.read_exact(buf).map_err(|e| {
+            self.error = Some(e);
+            eio!("Failed to read from reader")
+        })
+    }
+}
+
+/// A `Sink` adapter for `std::io::Write` that keeps the underlying I/O error
+///
+/// _Note: Single elements are written with a call to the writer each; wrap unbuffered writers into
+/// a `std::io::BufWriter`._
+#[cfg(feature = "std")]
+pub struct IoSink<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+#[cfg(feature = "std")]
+impl<W: Write> IoSink<W> {
+    /// Creates a new I/O sink over `writer`
+    pub fn new(writer: W) -> Self {
+        Self { writer, error: None }
+    }
+    /// Takes the last I/O error that occurred while writing
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+    /// Converts `result` into an `io::Result`, preferring the last I/O error over `Asn1DerError`
+    pub fn io_result<T>(&mut self, result: Result<T, Asn1DerError>) -> io::Result<T> {
+        result.map_err(|e| self.error.take().unwrap_or_else(|| e.into()))
+    }
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+#[cfg(feature = "std")]
+impl<W: Write> Sink for IoSink<W> {
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        self.write_all(&[e])
+    }
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        self.writer.write_all(bytes).map_err(|e| {
+            self.error = Some(e);
+            eio!("Failed to write to writer")
+        })
+    }
+}
@@ -1,19 +1,143 @@
 use crate::error::ErrorChain;
-use crate::{Asn1DerError, Sink, Source};
+use crate::{Asn1DerError, ReverseSink, Sink, Source};
+
+/// Options to relax the strict DER decoding rules for interoperability with non-conforming encoders
+///
+/// Each flag enables one specific laxness; the default value is strict DER (i.e. all flags are
+/// `false`) without any decoding limits.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DecodeOptions {
+    /// Accepts definite lengths that are encoded with more bytes than necessary
+    pub allow_non_minimal_lengths: bool,
+    /// Accepts any non-zero value (instead of only `0xff`) as BOOLEAN `TRUE`
+    pub allow_ber_booleans: bool,
+    /// Accepts INTEGERs with redundant leading `0x00` or `0xff` bytes
+    pub allow_non_minimal_integers: bool,
+    /// The limits to enforce while decoding untrusted input
+    pub limits: DecodeLimits,
+}
+impl DecodeOptions {
+    /// Strict DER decoding
+    pub const STRICT: Self = Self {
+        allow_non_minimal_lengths: false,
+        allow_ber_booleans: false,
+        allow_non_minimal_integers: false,
+        limits: DecodeLimits::UNLIMITED,
+    };
+    /// Lenient decoding that enables all laxness flags
+    pub const LENIENT: Self = Self {
+        allow_non_minimal_lengths: true,
+        allow_ber_booleans: true,
+        allow_non_minimal_integers: true,
+        limits: DecodeLimits::UNLIMITED,
+    };
+
+    /// Descends into a constructed object and returns the options to load its subobjects with
+    ///
+    /// _Note: Recursive `DerDecodable` implementations should call this once per nesting level, so
+    /// that `limits.max_depth` is enforced._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn nested(self) -> Result<Self, Asn1DerError> {
+        match self.limits.max_depth.checked_sub(1) {
+            Some(max_depth) => Ok(Self { limits: DecodeLimits { max_depth, ..self.limits }, ..self }),
+            None => Err(elimit!("The object exceeds the maximum nesting depth")),
+        }
+    }
+}
+
+/// Limits to protect against hostile input that would otherwise exhaust the memory or the stack
+///
+/// A limit that is exceeded results in a `LimitExceeded` error; the default value imposes no
+/// limits.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DecodeLimits {
+    /// The maximum length of an object (header and value)
+    pub max_object_len: usize,
+    /// The maximum nesting depth of constructed objects that are loaded as typed values
+    pub max_depth: usize,
+    /// The maximum amount of elements that are loaded into a single collection
+    pub max_elements: usize,
+    /// The maximum amount of bytes that may be allocated to hold a decoded object or value and all
+    /// of its subobjects together
+    pub max_alloc: usize,
+}
+impl DecodeLimits {
+    /// No limits
+    pub const UNLIMITED: Self =
+        Self { max_object_len: usize::MAX, max_depth: usize::MAX, max_elements: usize::MAX, max_alloc: usize::MAX };
+
+    /// Ensures that an object with `len` bytes does not exceed `max_object_len`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub(crate) fn check_object_len(&self, len: usize) -> Result<(), Asn1DerError> {
+        match len {
+            len if len <= self.max_object_len => Ok(()),
+            _ => Err(elimit!("The object exceeds the maximum object length")),
+        }
+    }
+    /// Ensures that a collection with `count` elements does not exceed `max_elements`
+    #[cfg(feature = "native_types")]
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub(crate) fn check_elements(&self, count: usize) -> Result<(), Asn1DerError> {
+        match count {
+            count if count <= self.max_elements => Ok(()),
+            _ => Err(elimit!("The collection exceeds the maximum amount of elements")),
+        }
+    }
+    /// Ensures that an allocation of `size` bytes does not exceed `max_alloc`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub(crate) fn check_alloc(&self, size: usize) -> Result<(), Asn1DerError> {
+        match size {
+            size if size <= self.max_alloc => Ok(()),
+            _ => Err(elimit!("The allocation exceeds the maximum allocation size")),
+        }
+    }
+    /// Draws an allocation of `size` bytes from the `max_alloc` budget and returns the limits for
+    /// the remaining budget
+    ///
+    /// _Note: Manual `DerDecodable` implementations that load subobjects without a `SequenceReader`
+    /// should draw the `allocated_len` of each subobject, so that `max_alloc` is enforced for the
+    /// whole object._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn allocate(self, size: usize) -> Result<Self, Asn1DerError> {
+        self.check_alloc(size)?;
+        let max_alloc = match self.max_alloc {
+            usize::MAX => usize::MAX,
+            // #implicit_validation: Since `size <= max_alloc`, the subtraction cannot underflow
+            max_alloc => max_alloc.saturating_sub(size),
+        };
+        Ok(Self { max_alloc, ..self })
+    }
+}
+impl Default for DecodeLimits {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
 
 /// A mod for ASN.1-length-coding
 pub mod length {
+    use crate::der::DecodeOptions;
     use crate::error::ErrorChain;
     use crate::{Asn1DerError, Sink, Source};
     use core::mem;
 
     /// The byte length of an `usize`
     const SIZE: usize = mem::size_of::<usize>();
+    /// The maximum length of a DER header (tag, complex length byte and length bytes)
+    pub(crate) const HEADER_MAX: usize = SIZE + 2;
 
     /// Tries to read the length or returns `None` if there are not enough bytes
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     #[cfg_attr(feature = "no_panic", inline(always))]
     pub fn decode<S: Source>(source: &mut S) -> Result<Option<usize>, Asn1DerError> {
+        decode_with(source, DecodeOptions::STRICT)
+    }
+    /// Tries to read the length according to `options` or returns `None` if there are not enough
+    /// bytes
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    #[cfg_attr(feature = "no_panic", inline(always))]
+    pub fn decode_with<S: Source>(source: &mut S, options: DecodeOptions) -> Result<Option<usize>, Asn1DerError> {
         // Read first byte
         let first = match source.read() {
             Ok(first) => first,
@@ -21,10 +145,12 @@ pub mod length {
         };
 
         // Check if we have a simple or a complex length
-        match first as usize {
-            len if len < 0b1000_0000 => Ok(Some(len)),
+        let (len, header_len) = match first as usize {
+            len if len < 0b1000_0000 => (len, 2),
+            // The indefinite length form is not a non-minimal length and thus never allowed in DER
+            0b1000_0000 => Err(einval!("The indefinite length form is not DER"))?,
             size if size & 0b0111_1111 > SIZE => {
-                Err(eunsupported!("The object length is greater than `usize::max_value()`"))
+                Err(eunsupported!("The object length is greater than `usize::max_value()`"))?
             }
             size => {
                 // Prepare buffer
@@ -40,15 +166,52 @@ pub mod length {
                 }
 
                 // Validate the length
+                // #implicit_validation: Since `size & 0b0111_1111 <= SIZE`, the addition cannot overflow
+                let header_len = (size & 0b0111_1111).saturating_add(2);
                 match usize::from_be_bytes(buf) {
+                    len if options.allow_non_minimal_lengths => (len, header_len),
                     // Complex lengths must not be used to represent a single byte length
                     len if len < 0b1000_0000 => Err(einval!("Encountered complex length < 128"))?,
                     // DER requires minimal encoding: the first byte of the length must be non-zero (otherwise a shorter
                     //  encoding would have been sufficient)
-                    len if buf[skip] == 0 => Err(einval!("Non-canonical DER: length uses more bytes than necessary"))?,
-                    len => Ok(Some(len)),
+                    _ if buf.get(skip) == Some(&0) => {
+                        Err(einval!("Non-canonical DER: length uses more bytes than necessary"))?
+                    }
+                    len => (len, header_len),
                 }
             }
+        };
+
+        // Check the object length (tag, length bytes and value); a saturated length can only pass if
+        // the object length is unlimited
+        options.limits.check_object_len(len.saturating_add(header_len))?;
+        Ok(Some(len))
+    }
+
+    /// Computes the length of a header (tag and length) from its first length byte
+    ///
+    /// _Note: Lengths with more bytes than supported are reported as two-byte headers, so that the
+    /// length decoder can reject them._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub(crate) fn header_len(first: u8) -> usize {
+        match first as usize {
+            first if first & 0b1000_0000 == 0 => 2,
+            // #implicit_validation: Since the amount of length bytes is at most 127, the addition cannot overflow
+            first => match (first & 0b0111_1111).saturating_add(2) {
+                header_len if header_len > HEADER_MAX => 2,
+                header_len => header_len,
+            },
+        }
+    }
+
+    /// Computes the amount of bytes necessary to encode `len`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn encoded_len(len: usize) -> usize {
+        match len {
+            len if len < 128 => 1,
+            // #implicit_validation: Since the amount of leading zero bytes cannot be larger than the
+            // amount of bytes in an `usize` (aka `SIZE`), the subtraction cannot overflow
+            len => SIZE.saturating_sub(len.leading_zeros() as usize / 8).saturating_add(1),
         }
     }
 
@@ -70,15 +233,71 @@ pub mod length {
 
                 // Write the length
                 sink.write(0x80 | size as u8).propagate(e!("Failed to write length byte"))?;
-                len.to_be_bytes()
-                    .iter()
-                    .skip(skip)
-                    .try_for_each(|b| sink.write(*b).propagate(e!("Failed to write length byte")))
+                let bytes = len.to_be_bytes();
+                sink.write_all(bytes.get(skip..).unwrap_or_default()).propagate(e!("Failed to write length bytes"))
             }
         }
     }
 }
 
+/// The header of a DER object
+///
+/// _Note: Decoding a header does not require the value bytes, so it can be used to route or size
+/// an object before its value is available._
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Header {
+    /// The object tag
+    pub tag: u8,
+    /// The length of the header (i.e. the tag and the length bytes)
+    pub header_len: usize,
+    /// The length of the value
+    pub value_len: usize,
+    /// Whether the object is constructed
+    pub constructed: bool,
+}
+impl Header {
+    /// Decodes the header at the beginning of `raw`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode(raw: &[u8]) -> Result<Self, Asn1DerError> {
+        Self::decode_from_source(&mut raw.iter())
+    }
+    /// Decodes the header at the beginning of `raw` according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_with(raw: &[u8], options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        Self::decode_from_source_with(&mut raw.iter(), options)
+    }
+    /// Reads a header from `source`
+    ///
+    /// _Note: Only the header bytes are consumed, so that `source` is positioned at the value._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_from_source<S: Source>(source: &mut S) -> Result<Self, Asn1DerError> {
+        Self::decode_from_source_with(source, DecodeOptions::STRICT)
+    }
+    /// Reads a header from `source` according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_from_source_with<S: Source>(source: &mut S, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let mut header_len = 0;
+        let mut source = source.counting_source(&mut header_len);
+
+        // Read tag and length
+        let tag = source.read().propagate(e!("Failed to read tag"))?;
+        let value_len = length::decode_with(&mut source, options)
+            .propagate(e!("Failed to decode length"))?
+            .ok_or(eio!("Truncated length"))?;
+
+        Ok(Self { tag, header_len, value_len, constructed: tag & 0b0010_0000 != 0 })
+    }
+
+    /// The total length of the object (i.e. the header and the value)
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn object_len(&self) -> Result<usize, Asn1DerError> {
+        match self.header_len.checked_add(self.value_len) {
+            Some(len) => Ok(len),
+            None => Err(eunsupported!("The object length would exceed `usize::max_value()`")),
+        }
+    }
+}
+
 /// An untyped DER object
 #[derive(Copy, Clone)]
 pub struct DerObject<'a> {
@@ -114,14 +333,26 @@ impl<'a> DerObject<'a> {
     #[doc(hidden)]
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn decode_at(raw: &'a [u8], header_start: usize) -> Result<Self, Asn1DerError> {
+        Self::decode_at_with(raw, header_start, DecodeOptions::STRICT)
+    }
+    /// Decodes an object from `raw` according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_with(raw: &'a [u8], options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        Self::decode_at_with(raw, 0, options)
+    }
+    /// Decodes an object from `&raw[header_start..]` according to `options`
+    #[doc(hidden)]
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_at_with(raw: &'a [u8], header_start: usize, options: DecodeOptions) -> Result<Self, Asn1DerError> {
         // Create iterator
         let mut value_start = header_start;
         let mut iter = raw.iter().skip(header_start).counting_source(&mut value_start);
 
         // Skip tag and read length
         let tag = iter.read().propagate(e!("Failed to read tag"))?;
-        let len =
-            length::decode(&mut iter).propagate(e!("Failed to decode length"))?.ok_or(eio!("Truncated length"))?;
+        let len = length::decode_with(&mut iter, options)
+            .propagate(e!("Failed to decode length"))?
+            .ok_or(eio!("Truncated length"))?;
         let value_end = match value_start.checked_add(len) {
             Some(value_end) => value_end,
             None => Err(eunsupported!("The object bounds would exceed `usize::max_value()`"))?,
@@ -155,24 +386,44 @@ impl<'a> DerObject<'a> {
 
         Ok(Self { raw, header, tag, value })
     }
+    /// Creates a view over an already validated object in `raw` with a header of `header_len` bytes
+    #[cfg(all(any(feature = "alloc", feature = "bytes"), not(feature = "no_panic")))]
+    pub(crate) fn from_parts(raw: &'a [u8], header_len: usize) -> Self {
+        // #implicit_validation: Since the object has been validated before, the header is always
+        // within `raw` and starts with the tag
+        let (header, value) = raw.split_at(header_len.min(raw.len()));
+        Self { raw, header, tag: header.first().copied().unwrap_or_default(), value }
+    }
     /// Reads a DER-TLV structure from `source` by parsing the length field and copying the
     /// necessary bytes into `sink` and returns a view over it
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn decode_from_source<A: Source, B: Sink + Into<&'a [u8]>>(
+        source: &mut A,
+        sink: B,
+    ) -> Result<Self, Asn1DerError> {
+        Self::decode_from_source_with(source, sink, DecodeOptions::STRICT)
+    }
+    /// Reads a DER-TLV structure from `source` according to `options` by parsing the length field
+    /// and copying the necessary bytes into `sink` and returns a view over it
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn decode_from_source_with<A: Source, B: Sink + Into<&'a [u8]>>(
         source: &mut A,
         mut sink: B,
+        options: DecodeOptions,
     ) -> Result<Self, Asn1DerError> {
         // Create a copying iterator and copy the tag
         let mut source = source.copying_source(&mut sink);
         source.copy_next().propagate(e!("Failed to read tag"))?;
 
         // Read the length and copy the value
-        let len =
-            length::decode(&mut source).propagate(e!("Failed to decode length"))?.ok_or(eio!("Truncated length"))?;
+        let len = length::decode_with(&mut source, options)
+            .propagate(e!("Failed to decode length"))?
+            .ok_or(eio!("Truncated length"))?;
+        options.limits.check_alloc(len).propagate(e!("Failed to copy object value"))?;
         source.copy_n(len).propagate(e!("Failed to copy object value"))?;
 
         // Load the object
-        Self::decode(sink.into()).propagate(e!("Failed to decode object"))
+        Self::decode_with(sink.into(), options).propagate(e!("Failed to decode object"))
     }
 
     /// The underlying raw slice
@@ -196,11 +447,57 @@ impl<'a> DerObject<'a> {
         self.value
     }
 
+    /// Creates a copy of `self` with `tag` as object tag
+    ///
+    /// _Note: This does not modify the underlying raw slice; i.e. `raw` and `header` still contain
+    /// the original tag._
+    #[cfg(feature = "native_types")]
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub(crate) fn with_tag(self, tag: u8) -> Self {
+        Self { tag, ..self }
+    }
+
+    /// Computes the length of a DER-TLV structure with a value of `len` bytes
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn tlv_len(len: usize) -> Result<usize, Asn1DerError> {
+        // #implicit_validation: The length of the length field is at most `SIZE + 1`
+        let header_len = length::encoded_len(len).saturating_add(1);
+        match header_len.checked_add(len) {
+            Some(tlv_len) => Ok(tlv_len),
+            None => Err(eunsupported!("The object length would exceed `usize::max_value()`")),
+        }
+    }
+
     /// Encodes `self` to `sink`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn encode<U: Sink>(&self, sink: &mut U) -> Result<(), Asn1DerError> {
-        Self::write(self.tag, self.value.len(), &mut self.value.iter(), sink)
-            .propagate(e!("Failed to write DER object"))
+        Self::write_slice(self.tag, self.value, sink).propagate(e!("Failed to write DER object"))
+    }
+
+    /// Writes a `tag`-`value` combination as DER-TLV structure into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_slice<S: Sink>(tag: u8, value: &[u8], sink: &mut S) -> Result<(), Asn1DerError> {
+        sink.write(tag).propagate(e!("Failed to write tag"))?;
+        length::encode(value.len(), sink).propagate(e!("Failed to write length"))?;
+        sink.write_all(value).propagate(e!("Failed to write value"))
+    }
+    /// Prepends the header of a DER-TLV structure with `tag` and a value of `len` bytes to `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_header_reverse<S: ReverseSink>(tag: u8, len: usize, sink: &mut S) -> Result<(), Asn1DerError> {
+        // Encode the header into a buffer that can hold the tag and the longest possible length
+        let (mut header, mut header_len) = ([0; length::HEADER_MAX], 0);
+        let mut header_sink = header.iter_mut().counting_sink(&mut header_len);
+        header_sink.write(tag).propagate(e!("Failed to write tag"))?;
+        length::encode(len, &mut header_sink).propagate(e!("Failed to write length"))?;
+
+        let header = header.get(..header_len).unwrap_or_default();
+        sink.prepend(header).propagate(e!("Failed to write header"))
+    }
+    /// Prepends a `tag`-`value` combination as DER-TLV structure to `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_reverse<S: ReverseSink>(tag: u8, value: &[u8], sink: &mut S) -> Result<(), Asn1DerError> {
+        sink.prepend(value).propagate(e!("Failed to write value"))?;
+        Self::write_header_reverse(tag, value.len(), sink)
     }
 
     /// Writes a `tag`-`len`-`value` combination as DER-TLV structure into `sink`
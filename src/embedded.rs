@@ -0,0 +1,245 @@
+//! Adapters for the `embedded-io` traits
+
+#[cfg(feature = "embedded_io_async")]
+use crate::data::CHUNK_SIZE;
+#[cfg(feature = "embedded_io_async")]
+use crate::der::length;
+#[cfg(feature = "embedded_io_async")]
+use crate::error::ErrorChain;
+#[cfg(all(feature = "embedded_io_async", feature = "native_types"))]
+use crate::typed::DerEncodable;
+#[cfg(all(feature = "embedded_io_async", feature = "native_types"))]
+use crate::SliceSink;
+use crate::{Asn1DerError, Sink, Source};
+#[cfg(feature = "embedded_io_async")]
+use crate::{DecodeOptions, DerObject};
+
+/// A `Source` adapter for `embedded_io::Read` that keeps the underlying error
+///
+/// _Note: Single elements are read with a call to the reader each._
+pub struct EmbeddedSource<R: embedded_io::Read> {
+    reader: R,
+    error: Option<R::Error>,
+}
+impl<R: embedded_io::Read> EmbeddedSource<R> {
+    /// Creates a new source over `reader`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(reader: R) -> Self {
+        Self { reader, error: None }
+    }
+    /// Takes the last error that occurred while reading
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn take_error(&mut self) -> Option<R::Error> {
+        self.error.take()
+    }
+    /// Returns the underlying reader
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+impl<R: embedded_io::Read> Source for EmbeddedSource<R> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read(&mut self) -> Result<u8, Asn1DerError> {
+        let mut buf = [0];
+        self.read_exact(&mut buf)?;
+        let [e] = buf;
+        Ok(e)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        // Don't use `embedded_io::Read::read_exact` since it panics on misbehaving readers
+        let mut pos = 0;
+        while let Some(rest @ [_, ..]) = buf.get_mut(pos..) {
+            match self.reader.read(rest) {
+                Ok(0) => return Err(eio!("Unexpected end of reader")),
+                Ok(read) => pos = pos.saturating_add(read),
+                Err(e) => {
+                    self.error = Some(e);
+                    return Err(eio!("Failed to read from reader"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `Sink` adapter for `embedded_io::Write` that keeps the underlying error
+///
+/// _Note: Single elements are written with a call to the writer each; the writer is not flushed._
+pub struct EmbeddedSink<W: embedded_io::Write> {
+    writer: W,
+    error: Option<W::Error>,
+}
+impl<W: embedded_io::Write> EmbeddedSink<W> {
+    /// Creates a new sink over `writer`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(writer: W) -> Self {
+        Self { writer, error: None }
+    }
+    /// Takes the last error that occurred while writing
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn take_error(&mut self) -> Option<W::Error> {
+        self.error.take()
+    }
+    /// Returns the underlying writer
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+impl<W: embedded_io::Write> Sink for EmbeddedSink<W> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        self.write_all(&[e])
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        // Don't use `embedded_io::Write::write_all` since it panics on misbehaving writers
+        let mut pos = 0;
+        while let Some(rest @ [_, ..]) = bytes.get(pos..) {
+            match self.writer.write(rest) {
+                Ok(0) => return Err(eio!("The writer is full")),
+                Ok(written) => pos = pos.saturating_add(written),
+                Err(e) => {
+                    self.error = Some(e);
+                    return Err(eio!("Failed to write to writer"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An adapter that decodes DER objects from an `embedded_io_async::Read`
+///
+/// _Note: Since `Source` is synchronous, this adapter reads whole objects instead of implementing
+/// `Source`._
+#[cfg(feature = "embedded_io_async")]
+pub struct AsyncEmbeddedSource<R: embedded_io_async::Read> {
+    reader: R,
+    error: Option<R::Error>,
+}
+#[cfg(feature = "embedded_io_async")]
+impl<R: embedded_io_async::Read> AsyncEmbeddedSource<R> {
+    /// Creates a new source over `reader`
+    pub fn new(reader: R) -> Self {
+        Self { reader, error: None }
+    }
+    /// Takes the last error that occurred while reading
+    pub fn take_error(&mut self) -> Option<R::Error> {
+        self.error.take()
+    }
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads exactly `buf.len()` bytes
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        let mut pos = 0;
+        while let Some(rest @ [_, ..]) = buf.get_mut(pos..) {
+            match self.reader.read(rest).await {
+                Ok(0) => return Err(eio!("Unexpected end of reader")),
+                Ok(read) => pos = pos.saturating_add(read),
+                Err(e) => {
+                    self.error = Some(e);
+                    return Err(eio!("Failed to read from reader"));
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Reads a DER-TLV structure by parsing the length field and copying the necessary bytes into
+    /// `sink` and returns a view over it
+    pub async fn decode<'a, S: Sink + Into<&'a [u8]>>(&mut self, sink: S) -> Result<DerObject<'a>, Asn1DerError> {
+        self.decode_with(sink, DecodeOptions::STRICT).await
+    }
+    /// Reads a DER-TLV structure according to `options` by parsing the length field and copying
+    /// the necessary bytes into `sink` and returns a view over it
+    pub async fn decode_with<'a, S: Sink + Into<&'a [u8]>>(
+        &mut self,
+        mut sink: S,
+        options: DecodeOptions,
+    ) -> Result<DerObject<'a>, Asn1DerError> {
+        // Read the tag and the first length byte
+        let mut header = [0; length::HEADER_MAX];
+        self.read_exact(header.get_mut(..2).unwrap_or_default()).await.propagate(e!("Failed to read header"))?;
+
+        // Read the remaining length bytes; oversized lengths are rejected by the length decoder
+        let header_len = length::header_len(header.get(1).copied().unwrap_or_default());
+        let length_bytes = header.get_mut(2..header_len).unwrap_or_default();
+        self.read_exact(length_bytes).await.propagate(e!("Failed to read length"))?;
+
+        // Decode the length and copy the header
+        let header = header.get(..header_len).unwrap_or_default();
+        let len = length::decode_with(&mut header.get(1..).unwrap_or_default().iter(), options)
+            .propagate(e!("Failed to decode length"))?
+            .ok_or(eio!("Truncated length"))?;
+        sink.write_all(header).propagate(e!("Failed to copy header"))?;
+
+        // Copy the value in chunks via a stack buffer
+        let (mut buf, mut remaining) = ([0; CHUNK_SIZE], len);
+        while remaining > 0 {
+            let chunk = buf.get_mut(..remaining.min(CHUNK_SIZE)).unwrap_or_default();
+            self.read_exact(chunk).await.propagate(e!("Failed to read object value"))?;
+            sink.write_all(chunk).propagate(e!("Failed to copy object value"))?;
+            remaining = remaining.saturating_sub(chunk.len());
+        }
+
+        // Load the object
+        DerObject::decode_with(sink.into(), options).propagate(e!("Failed to decode object"))
+    }
+}
+
+/// An adapter that writes DER objects to an `embedded_io_async::Write`
+///
+/// _Note: Since `Sink` is synchronous, this adapter writes whole objects instead of implementing
+/// `Sink`; the writer is not flushed._
+#[cfg(feature = "embedded_io_async")]
+pub struct AsyncEmbeddedSink<W: embedded_io_async::Write> {
+    writer: W,
+    error: Option<W::Error>,
+}
+#[cfg(feature = "embedded_io_async")]
+impl<W: embedded_io_async::Write> AsyncEmbeddedSink<W> {
+    /// Creates a new sink over `writer`
+    pub fn new(writer: W) -> Self {
+        Self { writer, error: None }
+    }
+    /// Takes the last error that occurred while writing
+    pub fn take_error(&mut self) -> Option<W::Error> {
+        self.error.take()
+    }
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes all `bytes`
+    pub async fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        let mut pos = 0;
+        while let Some(rest @ [_, ..]) = bytes.get(pos..) {
+            match self.writer.write(rest).await {
+                Ok(0) => return Err(eio!("The writer is full")),
+                Ok(written) => pos = pos.saturating_add(written),
+                Err(e) => {
+                    self.error = Some(e);
+                    return Err(eio!("Failed to write to writer"));
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Writes `object`
+    pub async fn write_object(&mut self, object: DerObject<'_>) -> Result<(), Asn1DerError> {
+        self.write_all(object.raw()).await.propagate(e!("Failed to write object"))
+    }
+    /// Encodes `value` into `buf` and writes the encoded object
+    #[cfg(feature = "native_types")]
+    pub async fn encode<T: DerEncodable>(&mut self, value: &T, buf: &mut [u8]) -> Result<(), Asn1DerError> {
+        let mut pos = 0;
+        value.encode(&mut SliceSink::new(buf, &mut pos)).propagate(e!("Failed to encode object"))?;
+        self.write_all(buf.get(..pos).unwrap_or_default()).await.propagate(e!("Failed to write object"))
+    }
+}
@@ -1,6 +1,8 @@
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use alloc::boxed::Box;
 use core::fmt::{self, Display, Formatter};
 #[cfg(feature = "std")]
-use std::error::Error;
+use std::{error::Error, io};
 
 /// Creates a static error description with file and line information
 #[doc(hidden)]
@@ -19,7 +21,7 @@ macro_rules! e {
 #[macro_export]
 macro_rules! eio {
     ($str:expr) => {
-        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::InOutError(e!($str)))
+        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::InOutError($crate::e!($str)))
     };
 }
 /// Creates an `InvalidData` variant
@@ -27,7 +29,7 @@ macro_rules! eio {
 #[macro_export]
 macro_rules! einval {
     ($str:expr) => {
-        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::InvalidData(e!($str)))
+        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::InvalidData($crate::e!($str)))
     };
 }
 /// Creates an `Unsupported` variant
@@ -35,7 +37,15 @@ macro_rules! einval {
 #[macro_export]
 macro_rules! eunsupported {
     ($str:expr) => {
-        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::Unsupported(e!($str)))
+        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::Unsupported($crate::e!($str)))
+    };
+}
+/// Creates a `LimitExceeded` variant
+#[doc(hidden)]
+#[macro_export]
+macro_rules! elimit {
+    ($str:expr) => {
+        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::LimitExceeded($crate::e!($str)))
     };
 }
 /// Creates an `Other` variant
@@ -43,7 +53,7 @@ macro_rules! eunsupported {
 #[macro_export]
 macro_rules! eother {
     ($str:expr) => {
-        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::Other(e!($str)))
+        $crate::error::Asn1DerError::new($crate::error::Asn1DerErrorVariant::Other($crate::e!($str)))
     };
 }
 
@@ -57,15 +67,16 @@ pub trait ErrorChain {
 impl<T> ErrorChain for Result<T, Asn1DerError> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn propagate(self, _desc: &'static str) -> Self {
-        #[cfg(any(not(feature = "std"), feature = "no_panic"))]
+        #[cfg(any(not(feature = "alloc"), feature = "no_panic"))]
         return self;
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             self.map_err(|e| {
                 let new_error = match e.error {
                     Asn1DerErrorVariant::InOutError(_) => Asn1DerErrorVariant::InOutError(_desc),
                     Asn1DerErrorVariant::InvalidData(_) => Asn1DerErrorVariant::InvalidData(_desc),
                     Asn1DerErrorVariant::Unsupported(_) => Asn1DerErrorVariant::Unsupported(_desc),
+                    Asn1DerErrorVariant::LimitExceeded(_) => Asn1DerErrorVariant::LimitExceeded(_desc),
                     Asn1DerErrorVariant::Other(_) => Asn1DerErrorVariant::Other(_desc),
                 };
                 Asn1DerError { error: new_error, source: Some(ErrorSource::new(e)) }
@@ -75,7 +86,11 @@ impl<T> ErrorChain for Result<T, Asn1DerError> {
 }
 
 /// An `Asn1DerError` variant
+///
+/// _Note: This enum is non-exhaustive since new kinds of errors (like `LimitExceeded`) may be added
+/// in future versions; matches must include a wildcard arm._
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Asn1DerErrorVariant {
     /// An in-out error occurred (e.g. failed to read/write some bytes)
     InOutError(&'static str),
@@ -83,6 +98,8 @@ pub enum Asn1DerErrorVariant {
     InvalidData(&'static str),
     /// The object type or length is not supported by this implementation
     Unsupported(&'static str),
+    /// The data exceeds a configured decoding limit
+    LimitExceeded(&'static str),
     /// An unspecified error
     Other(&'static str),
 }
@@ -92,6 +109,7 @@ impl Display for Asn1DerErrorVariant {
             Asn1DerErrorVariant::InOutError(desc) => write!(f, "I/O error {}", desc),
             Asn1DerErrorVariant::InvalidData(desc) => write!(f, "Invalid encoding {}", desc),
             Asn1DerErrorVariant::Unsupported(desc) => write!(f, "Unsupported {}", desc),
+            Asn1DerErrorVariant::LimitExceeded(desc) => write!(f, "Limit exceeded {}", desc),
             Asn1DerErrorVariant::Other(desc) => write!(f, "Other {}", desc),
         }
     }
@@ -101,14 +119,14 @@ impl Display for Asn1DerErrorVariant {
 #[doc(hidden)]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ErrorSource {
-    #[cfg(any(not(feature = "std"), feature = "no_panic"))]
+    #[cfg(any(not(feature = "alloc"), feature = "no_panic"))]
     inner: &'static str,
-    #[cfg(all(feature = "std", not(feature = "no_panic")))]
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
     inner: Box<Asn1DerError>,
 }
 impl ErrorSource {
     /// Creates a new error source
-    #[cfg(all(feature = "std", not(feature = "no_panic")))]
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
     pub fn new(e: Asn1DerError) -> Self {
         Self { inner: Box::new(e) }
     }
@@ -141,9 +159,20 @@ impl Display for Asn1DerError {
 impl Error for Asn1DerError {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        #[cfg(any(not(feature = "std"), feature = "no_panic"))]
+        #[cfg(any(not(feature = "alloc"), feature = "no_panic"))]
         return None;
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         return self.source.as_ref().map(|s| s.inner.as_ref() as _);
     }
 }
+#[cfg(feature = "std")]
+impl From<Asn1DerError> for io::Error {
+    fn from(error: Asn1DerError) -> Self {
+        let kind = match error.error {
+            Asn1DerErrorVariant::InvalidData(_) | Asn1DerErrorVariant::LimitExceeded(_) => io::ErrorKind::InvalidData,
+            Asn1DerErrorVariant::Unsupported(_) => io::ErrorKind::Unsupported,
+            Asn1DerErrorVariant::InOutError(_) | Asn1DerErrorVariant::Other(_) => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, error)
+    }
+}
@@ -0,0 +1,133 @@
+//! A resumable push parser that splits a byte stream that arrives in arbitrary chunks into DER
+//! objects
+
+use crate::der::length;
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, DecodeOptions};
+use core::ops::Range;
+
+/// The progress of an `IncrementalParser` after a call to `feed`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Progress {
+    /// The entire input has been consumed and at least `n` more bytes are necessary to complete the
+    /// current object
+    NeedMore(usize),
+    /// An object has been completed after consuming `consumed` bytes of the input
+    Complete {
+        /// The position of the object within the stream
+        range: Range<usize>,
+        /// The amount of bytes that have been consumed from the input
+        consumed: usize,
+    },
+}
+
+/// The parser state
+#[derive(Copy, Clone, Debug)]
+enum State {
+    /// The header is being read
+    Header { buf: [u8; length::HEADER_MAX], len: usize },
+    /// The value is being skipped
+    Value { remaining: usize },
+}
+
+/// A resumable push parser that splits a byte stream into DER objects without buffering it
+///
+/// Every byte is inspected at most once: the parser keeps the partially read header across calls,
+/// and the value bytes are only counted. The caller owns the stream bytes and can view a completed
+/// object via `DerObject::decode` on its range.
+#[derive(Clone, Debug)]
+pub struct IncrementalParser {
+    state: State,
+    start: usize,
+    pos: usize,
+    options: DecodeOptions,
+}
+impl IncrementalParser {
+    /// Creates a new parser at stream position `0`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new() -> Self {
+        Self::with_options(DecodeOptions::STRICT)
+    }
+    /// Creates a new parser at stream position `0` that decodes according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn with_options(options: DecodeOptions) -> Self {
+        Self { state: State::Header { buf: [0; length::HEADER_MAX], len: 0 }, start: 0, pos: 0, options }
+    }
+
+    /// The current stream position (i.e. the amount of bytes consumed so far)
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+    /// Whether the parser is between two objects (i.e. the stream can end here without truncating
+    /// an object)
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn is_at_boundary(&self) -> bool {
+        matches!(self.state, State::Header { len: 0, .. })
+    }
+
+    /// Feeds the next `input` bytes of the stream into the parser
+    ///
+    /// If an object is completed, the parser stops and returns its range together with the amount
+    /// of consumed bytes; the remaining input must then be fed again.
+    ///
+    /// _Note: After an error, the stream cannot be resynchronized and the parser must not be fed
+    /// again._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn feed(&mut self, mut input: &[u8]) -> Result<Progress, Asn1DerError> {
+        let mut consumed = 0usize;
+        loop {
+            match self.state {
+                State::Header { ref mut buf, ref mut len } => {
+                    // Read the header byte-wise until it is complete; its length is known after the first length byte
+                    let header_len = match buf.get(1) {
+                        Some(first) if *len >= 2 => length::header_len(*first),
+                        _ => 2,
+                    };
+                    if *len < header_len {
+                        let (next, rest) = match input.split_first() {
+                            Some((next, rest)) => (*next, rest),
+                            None => return Ok(Progress::NeedMore(header_len.saturating_sub(*len))),
+                        };
+                        *buf.get_mut(*len).ok_or(eother!("The header is longer than expected"))? = next;
+                        // #implicit_validation: Since `len < header_len <= HEADER_MAX`, the addition cannot overflow
+                        *len = len.saturating_add(1);
+                        input = rest;
+                        consumed = consumed.saturating_add(1);
+                        self.pos = self.pos.checked_add(1).ok_or(eunsupported!("The stream position is too large"))?;
+                        continue;
+                    }
+
+                    // Decode the length
+                    let mut length_bytes = buf.get(1..*len).unwrap_or_default().iter();
+                    let remaining = length::decode_with(&mut length_bytes, self.options)
+                        .propagate(e!("Failed to decode length"))?
+                        .ok_or(eio!("Truncated length"))?;
+                    self.state = State::Value { remaining };
+                }
+                State::Value { ref mut remaining } => {
+                    // Skip the available value bytes
+                    let skip = (*remaining).min(input.len());
+                    *remaining = remaining.saturating_sub(skip);
+                    consumed = consumed.saturating_add(skip);
+                    self.pos = self.pos.checked_add(skip).ok_or(eunsupported!("The stream position is too large"))?;
+                    if *remaining > 0 {
+                        return Ok(Progress::NeedMore(*remaining));
+                    }
+
+                    // Complete the object
+                    let range = self.start..self.pos;
+                    self.start = self.pos;
+                    self.state = State::Header { buf: [0; length::HEADER_MAX], len: 0 };
+                    return Ok(Progress::Complete { range, consumed });
+                }
+            }
+        }
+    }
+}
+impl Default for IncrementalParser {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,18 +2,53 @@
 // Handle no_std if set
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[macro_use]
 #[doc(hidden)]
 pub mod error;
+pub mod ber;
+pub mod cer;
 mod data;
 #[doc(hidden)]
 pub mod der;
+#[cfg(feature = "embedded_io")]
+pub mod embedded;
+pub mod incremental;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+mod owned;
+pub mod patching;
+#[cfg(all(feature = "bytes", not(feature = "no_panic")))]
+pub mod shared;
+#[cfg(all(feature = "tokio_io", not(feature = "no_panic")))]
+pub mod tokio_io;
+pub mod traversal;
 #[cfg(feature = "native_types")]
 pub mod typed;
 
 // Reexport common types
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+pub use crate::ber::BerObject;
+#[cfg(feature = "heapless")]
+pub use crate::data::HeaplessBacking;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 pub use crate::data::VecBacking;
-pub use crate::data::{CopyingSource, CountingSource, Sink, SliceSink, Source};
-pub use crate::der::DerObject;
+pub use crate::data::{
+    CopyingSource, CountingSource, PatchSink, ReverseSink, ReverseSliceSink, Sink, SliceSink, Source,
+};
+#[cfg(feature = "std")]
+pub use crate::data::{IoSink, IoSource};
+pub use crate::der::{DecodeLimits, DecodeOptions, DerObject, Header};
+#[cfg(feature = "embedded_io_async")]
+pub use crate::embedded::{AsyncEmbeddedSink, AsyncEmbeddedSource};
+#[cfg(feature = "embedded_io")]
+pub use crate::embedded::{EmbeddedSink, EmbeddedSource};
 pub use crate::error::{Asn1DerError, Asn1DerErrorVariant, ErrorChain};
+pub use crate::incremental::{IncrementalParser, Progress};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::owned::DerObjectBuf;
+pub use crate::patching::PatchingEncoder;
+#[cfg(all(feature = "bytes", not(feature = "no_panic")))]
+pub use crate::shared::SharedDerObject;
+#[cfg(all(feature = "tokio_io", not(feature = "no_panic")))]
+pub use crate::tokio_io::AsyncDerReader;
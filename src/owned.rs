@@ -0,0 +1,83 @@
+//! Owned DER objects that can outlive the data they were decoded from
+
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, DecodeOptions, DerObject};
+use alloc::{boxed::Box, vec::Vec};
+
+/// An owned DER object
+///
+/// The object owns its backing bytes (e.g. a `Vec<u8>` or a `Box<[u8]>`), so that it can be stored
+/// in long-lived structs or sent over channels; `object` provides a `DerObject` view over it.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DerObjectBuf<B = Vec<u8>> {
+    raw: B,
+    header_len: usize,
+}
+impl<B: AsRef<[u8]>> DerObjectBuf<B> {
+    /// Takes ownership of `raw` which must contain exactly one DER object
+    pub fn decode(raw: B) -> Result<Self, Asn1DerError> {
+        Self::decode_with(raw, DecodeOptions::STRICT)
+    }
+    /// Takes ownership of `raw` which must contain exactly one DER object according to `options`
+    pub fn decode_with(raw: B, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let object = DerObject::decode_with(raw.as_ref(), options).propagate(e!("Failed to decode object"))?;
+        if object.raw().len() != raw.as_ref().len() {
+            Err(einval!("The object is followed by trailing data"))?;
+        }
+
+        let header_len = object.header().len();
+        Ok(Self { raw, header_len })
+    }
+
+    /// A view over the object
+    pub fn object(&self) -> DerObject<'_> {
+        DerObject::from_parts(self.raw.as_ref(), self.header_len)
+    }
+    /// The underlying raw bytes
+    pub fn raw(&self) -> &[u8] {
+        self.raw.as_ref()
+    }
+    /// The object header
+    pub fn header(&self) -> &[u8] {
+        self.object().header()
+    }
+    /// The object tag
+    pub fn tag(&self) -> u8 {
+        self.object().tag()
+    }
+    /// The object value
+    pub fn value(&self) -> &[u8] {
+        self.object().value()
+    }
+    /// Returns the underlying backing
+    pub fn into_inner(self) -> B {
+        self.raw
+    }
+}
+impl<B: AsRef<[u8]>> AsRef<[u8]> for DerObjectBuf<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.raw.as_ref()
+    }
+}
+impl<'a> From<DerObject<'a>> for DerObjectBuf<Vec<u8>> {
+    fn from(object: DerObject<'a>) -> Self {
+        Self { raw: object.raw().to_vec(), header_len: object.header().len() }
+    }
+}
+impl<'a> From<DerObject<'a>> for DerObjectBuf<Box<[u8]>> {
+    fn from(object: DerObject<'a>) -> Self {
+        Self { raw: object.raw().into(), header_len: object.header().len() }
+    }
+}
+impl From<DerObjectBuf<Vec<u8>>> for DerObjectBuf<Box<[u8]>> {
+    fn from(object: DerObjectBuf<Vec<u8>>) -> Self {
+        Self { raw: object.raw.into_boxed_slice(), header_len: object.header_len }
+    }
+}
+
+impl<'a> DerObject<'a> {
+    /// Copies the object into an owned `DerObjectBuf`
+    pub fn to_buf(&self) -> DerObjectBuf {
+        DerObjectBuf::from(*self)
+    }
+}
@@ -0,0 +1,122 @@
+//! A single-pass encoder for contiguous buffers
+//!
+//! Constructed objects are usually written by computing the length of their contents first (see
+//! `DerEncodable::encoded_len`). The `PatchingEncoder` instead reserves a single length byte, writes
+//! the contents and patches the length afterwards; if the length needs more than one byte, the
+//! contents are shifted accordingly. This way, nested constructed objects are written in a single
+//! pass.
+
+use crate::der::length;
+use crate::error::ErrorChain;
+#[cfg(feature = "native_types")]
+use crate::typed::DerEncodable;
+use crate::{Asn1DerError, DerObject, PatchSink};
+use core::mem;
+
+/// The tag of a `SEQUENCE`
+const SEQUENCE: u8 = 0x30;
+/// The tag of a `SET`
+const SET: u8 = 0x31;
+
+/// An encoder that writes constructed objects in a single pass by back-patching their lengths
+///
+/// ```rust
+/// # use asn1_der::{ DerObject, PatchingEncoder, SliceSink };
+/// // SEQUENCE { INTEGER 7, [0] EXPLICIT SEQUENCE { BOOLEAN TRUE } }
+/// let (mut buf, mut len) = ([0; 16], 0);
+/// let mut sink = SliceSink::new(&mut buf, &mut len);
+/// PatchingEncoder::new(&mut sink)
+///     .sequence(|encoder| {
+///         encoder.write(0x02, b"\x07")?;
+///         encoder.constructed(0xa0, |encoder| encoder.sequence(|encoder| encoder.write(0x01, b"\xff")))
+///     })
+///     .unwrap();
+/// assert_eq!(&buf[..len], b"\x30\x0a\x02\x01\x07\xa0\x05\x30\x03\x01\x01\xff");
+/// ```
+pub struct PatchingEncoder<'a, S: PatchSink> {
+    sink: &'a mut S,
+}
+impl<'a, S: PatchSink> PatchingEncoder<'a, S> {
+    /// Creates a new encoder that writes into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(sink: &'a mut S) -> Self {
+        Self { sink }
+    }
+
+    /// Writes a primitive object with `tag` and `value`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write(&mut self, tag: u8, value: &[u8]) -> Result<(), Asn1DerError> {
+        DerObject::write_slice(tag, value, self.sink).propagate(e!("Failed to write object"))
+    }
+    /// Encodes `value`
+    #[cfg(feature = "native_types")]
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn encode<T: DerEncodable>(&mut self, value: &T) -> Result<(), Asn1DerError> {
+        value.encode(self.sink).propagate(e!("Failed to encode object"))
+    }
+
+    /// Writes a constructed object with `tag` whose subobjects are written by `contents`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn constructed<F>(&mut self, tag: u8, contents: F) -> Result<(), Asn1DerError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Asn1DerError>,
+    {
+        // Write the tag and reserve a single byte for the length
+        self.sink.write(tag).propagate(e!("Failed to write tag"))?;
+        let length_start = self.sink.written_mut().len();
+        self.sink.write(0).propagate(e!("Failed to reserve length"))?;
+
+        // Write the subobjects and patch the length
+        contents(self).propagate(e!("Failed to write subobjects"))?;
+        self.patch_length(length_start).propagate(e!("Failed to patch length"))
+    }
+    /// Writes a `SEQUENCE` whose subobjects are written by `contents`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn sequence<F>(&mut self, contents: F) -> Result<(), Asn1DerError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Asn1DerError>,
+    {
+        self.constructed(SEQUENCE, contents)
+    }
+    /// Writes a `SET` whose subobjects are written by `contents`
+    ///
+    /// _Note: DER requires the subobjects to be written in canonical order; this is not checked._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn set<F>(&mut self, contents: F) -> Result<(), Asn1DerError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Asn1DerError>,
+    {
+        self.constructed(SET, contents)
+    }
+
+    /// Patches the reserved length byte at `length_start` with the length of the subsequent value
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn patch_length(&mut self, length_start: usize) -> Result<(), Asn1DerError> {
+        // #implicit_validation: the value starts after the reserved length byte which has been
+        // written and thus cannot overflow
+        let value_start = length_start.saturating_add(1);
+        let len = match self.sink.written_mut().len().checked_sub(value_start) {
+            Some(len) => len,
+            None => Err(einval!("The sink has been modified during encoding"))?,
+        };
+
+        // Grow the buffer and shift the value if the length needs more than the reserved byte
+        let extra = length::encoded_len(len).saturating_sub(1);
+        let padding = [0; mem::size_of::<usize>()];
+        self.sink.write_all(padding.get(..extra).unwrap_or_default()).propagate(e!("Failed to grow length"))?;
+        let value = match self.sink.written_mut().get_mut(value_start..) {
+            Some(value) => value,
+            None => Err(einval!("The sink has been modified during encoding"))?,
+        };
+        if extra <= value.len() {
+            value.rotate_right(extra);
+        }
+
+        // Write the length
+        let header = match self.sink.written_mut().get_mut(length_start..) {
+            Some(header) => header,
+            None => Err(einval!("The sink has been modified during encoding"))?,
+        };
+        length::encode(len, &mut header.iter_mut()).propagate(e!("Failed to write length"))
+    }
+}
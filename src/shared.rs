@@ -0,0 +1,102 @@
+//! Reference-counted DER objects that share the bytes of the frame they were decoded from
+
+use crate::error::ErrorChain;
+use crate::{Asn1DerError, DecodeOptions, DerObject};
+use bytes::Bytes;
+
+/// A DER object backed by `bytes::Bytes`
+///
+/// The header, the value and the children are cheap, reference-counted slices of the original
+/// frame instead of borrowed views or copies.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SharedDerObject {
+    raw: Bytes,
+    header_len: usize,
+    options: DecodeOptions,
+}
+impl SharedDerObject {
+    /// Takes `raw` which must contain exactly one DER object
+    pub fn decode(raw: Bytes) -> Result<Self, Asn1DerError> {
+        Self::decode_with(raw, DecodeOptions::STRICT)
+    }
+    /// Takes `raw` which must contain exactly one DER object according to `options`
+    pub fn decode_with(mut raw: Bytes, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let object = Self::decode_next_with(&mut raw, options)?;
+        match raw.is_empty() {
+            true => Ok(object),
+            false => Err(einval!("The object is followed by trailing data")),
+        }
+    }
+    /// Splits the next object off the front of `frame`
+    pub fn decode_next(frame: &mut Bytes) -> Result<Self, Asn1DerError> {
+        Self::decode_next_with(frame, DecodeOptions::STRICT)
+    }
+    /// Splits the next object off the front of `frame` according to `options`
+    ///
+    /// _Note: `frame` is only modified if the object is valid._
+    pub fn decode_next_with(frame: &mut Bytes, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let object = DerObject::decode_with(frame, options).propagate(e!("Failed to decode object"))?;
+        let (len, header_len) = (object.raw().len(), object.header().len());
+        Ok(Self { raw: frame.split_to(len), header_len, options })
+    }
+
+    /// A view over the object
+    pub fn object(&self) -> DerObject<'_> {
+        DerObject::from_parts(&self.raw, self.header_len)
+    }
+    /// The underlying raw bytes
+    pub fn raw(&self) -> &Bytes {
+        &self.raw
+    }
+    /// The object header
+    pub fn header(&self) -> Bytes {
+        self.raw.slice(..self.header_len)
+    }
+    /// The object tag
+    pub fn tag(&self) -> u8 {
+        self.object().tag()
+    }
+    /// The object value
+    pub fn value(&self) -> Bytes {
+        self.raw.slice(self.header_len..)
+    }
+    /// An iterator over the subobjects within the value (e.g. the elements of a `SEQUENCE`)
+    ///
+    /// _Note: The subobjects are decoded with the options of `self`; the iterator stops after the
+    /// first error._
+    pub fn children(&self) -> SharedChildren {
+        SharedChildren { value: self.value(), options: self.options }
+    }
+    /// Returns the underlying raw bytes
+    pub fn into_bytes(self) -> Bytes {
+        self.raw
+    }
+}
+impl AsRef<[u8]> for SharedDerObject {
+    fn as_ref(&self) -> &[u8] {
+        &self.raw
+    }
+}
+
+/// An iterator over the subobjects within the value of a `SharedDerObject`
+#[derive(Clone, Debug)]
+pub struct SharedChildren {
+    value: Bytes,
+    options: DecodeOptions,
+}
+impl Iterator for SharedChildren {
+    type Item = Result<SharedDerObject, Asn1DerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.value.is_empty() {
+            return None;
+        }
+
+        // Stop after the first error
+        let child = SharedDerObject::decode_next_with(&mut self.value, self.options);
+        if child.is_err() {
+            self.value.clear();
+        }
+        Some(child)
+    }
+}
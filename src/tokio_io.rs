@@ -0,0 +1,75 @@
+//! An asynchronous reader for back-to-back DER objects on a `tokio::io::AsyncRead`
+
+use crate::der::length;
+use crate::{DecodeOptions, DerObject};
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Reads complete DER objects from an asynchronous reader
+///
+/// Each object is read by parsing its header, checking its total length against a configurable
+/// maximum and then reading exactly the value; no bytes beyond the object are consumed.
+pub struct AsyncDerReader<R: AsyncRead + Unpin> {
+    reader: R,
+    max_len: usize,
+}
+impl<R: AsyncRead + Unpin> AsyncDerReader<R> {
+    /// The default maximum object length (header and value)
+    pub const DEFAULT_MAX_LEN: usize = 64 * 1024;
+
+    /// Creates a new reader over `reader` with the default maximum object length
+    pub fn new(reader: R) -> Self {
+        Self::with_max_len(reader, Self::DEFAULT_MAX_LEN)
+    }
+    /// Creates a new reader over `reader` that rejects objects longer than `max_len` bytes
+    pub fn with_max_len(reader: R, max_len: usize) -> Self {
+        Self { reader, max_len }
+    }
+    /// The maximum object length (header and value)
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next object and returns its raw bytes, or `None` if the reader ended before the
+    /// next object
+    ///
+    /// _Note: The returned bytes are a single valid DER object that can be viewed via
+    /// `DerObject::decode`._
+    pub async fn read_object(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self.read_object_with(DecodeOptions::STRICT).await
+    }
+    /// Reads the next object according to `options` and returns its raw bytes, or `None` if the
+    /// reader ended before the next object
+    pub async fn read_object_with(&mut self, options: DecodeOptions) -> io::Result<Option<Vec<u8>>> {
+        // Read the tag or detect a clean end of the stream
+        let mut header = [0; length::HEADER_MAX];
+        match self.reader.read(&mut header[..1]).await? {
+            0 => return Ok(None),
+            _ => self.reader.read_exact(&mut header[1..2]).await?,
+        };
+
+        // Read the remaining length bytes; oversized lengths are rejected by the length decoder
+        let header_len = length::header_len(header[1]);
+        self.reader.read_exact(&mut header[2..header_len]).await?;
+
+        // Decode the length and check the total length before allocating
+        let len = length::decode_with(&mut header[1..header_len].iter(), options)?.ok_or(eio!("Truncated length"))?;
+        let total_len = match header_len.checked_add(len) {
+            Some(total_len) if total_len <= self.max_len => total_len,
+            _ => Err(elimit!("The object is longer than the maximum object length"))?,
+        };
+
+        // Read the value
+        let mut object = Vec::with_capacity(total_len);
+        object.extend_from_slice(&header[..header_len]);
+        object.resize(total_len, 0);
+        self.reader.read_exact(&mut object[header_len..]).await?;
+
+        DerObject::decode_with(&object, options)?;
+        Ok(Some(object))
+    }
+}
@@ -0,0 +1,230 @@
+//! Header-based traversal of huge DER structures that only loads the subtrees that are requested
+//!
+//! Both traversals walk the tree by decoding headers and skip values by their length: a
+//! `SliceTraversal` walks an in-memory or memory-mapped slice, a `SeekTraversal` walks a
+//! `Read + Seek` reader and seeks over the values.
+
+use crate::der::Header;
+use crate::error::ErrorChain;
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+use crate::IoSource;
+use crate::{Asn1DerError, DecodeOptions, DerObject};
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// A node within a traversed structure
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Node {
+    /// The position of the node header
+    pub offset: u64,
+    /// The node header
+    pub header: Header,
+}
+impl Node {
+    /// The position of the node value
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn value_offset(&self) -> Result<u64, Asn1DerError> {
+        Self::add(self.offset, self.header.header_len)
+    }
+    /// The position after the node (i.e. the position of the next sibling)
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn end(&self) -> Result<u64, Asn1DerError> {
+        Self::add(self.offset, self.header.object_len()?)
+    }
+
+    /// Adds `len` to `offset`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn add(offset: u64, len: usize) -> Result<u64, Asn1DerError> {
+        match u64::try_from(len).ok().and_then(|len| offset.checked_add(len)) {
+            Some(end) => Ok(end),
+            None => Err(eunsupported!("The node bounds would exceed `u64::max_value()`")),
+        }
+    }
+}
+
+/// A traversal over a slice, e.g. a memory-mapped file
+#[derive(Copy, Clone)]
+pub struct SliceTraversal<'a> {
+    raw: &'a [u8],
+    options: DecodeOptions,
+}
+impl<'a> SliceTraversal<'a> {
+    /// Creates a new traversal over `raw`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(raw: &'a [u8]) -> Self {
+        Self::with_options(raw, DecodeOptions::STRICT)
+    }
+    /// Creates a new traversal over `raw` that decodes according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn with_options(raw: &'a [u8], options: DecodeOptions) -> Self {
+        Self { raw, options }
+    }
+
+    /// Decodes the node at `offset`
+    ///
+    /// _Note: The node must fit into the slice, but its value is not inspected._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn node_at(&self, offset: u64) -> Result<Node, Asn1DerError> {
+        let raw = usize::try_from(offset).ok().and_then(|offset| self.raw.get(offset..));
+        let raw = raw.ok_or(eio!("The node offset is beyond the end of the slice"))?;
+        let header = Header::decode_with(raw, self.options).propagate(e!("Failed to decode header"))?;
+        match header.object_len()? {
+            len if len <= raw.len() => Ok(Node { offset, header }),
+            _ => Err(eio!("The node is truncated")),
+        }
+    }
+    /// The node at the beginning of the slice
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn root(&self) -> Result<Node, Asn1DerError> {
+        self.node_at(0)
+    }
+    /// An iterator over the direct children of `node`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn children(&self, node: &Node) -> Result<SliceChildren<'a>, Asn1DerError> {
+        Ok(SliceChildren { traversal: *self, pos: node.value_offset()?, end: node.end()? })
+    }
+    /// The subtree of `node` as object
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn object(&self, node: &Node) -> Result<DerObject<'a>, Asn1DerError> {
+        let (start, end) = (usize::try_from(node.offset).ok(), usize::try_from(node.end()?).ok());
+        let raw = match (start, end) {
+            (Some(start), Some(end)) => self.raw.get(start..end),
+            _ => None,
+        };
+        let raw = raw.ok_or(eio!("The node is beyond the end of the slice"))?;
+        DerObject::decode_with(raw, self.options).propagate(e!("Failed to decode object"))
+    }
+}
+#[cfg(feature = "memmap2")]
+impl<'a> From<&'a memmap2::Mmap> for SliceTraversal<'a> {
+    fn from(mmap: &'a memmap2::Mmap) -> Self {
+        Self::new(mmap)
+    }
+}
+
+/// An iterator over the direct children of a node within a slice
+pub struct SliceChildren<'a> {
+    traversal: SliceTraversal<'a>,
+    pos: u64,
+    end: u64,
+}
+impl<'a> Iterator for SliceChildren<'a> {
+    type Item = Result<Node, Asn1DerError>;
+
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+
+        // Stop after the first error
+        let child = self.traversal.node_at(self.pos).and_then(|child| match child.end()? {
+            end if end <= self.end => Ok((child, end)),
+            _ => Err(einval!("The child exceeds its parent")),
+        });
+        match child {
+            Ok((child, end)) => {
+                self.pos = end;
+                Some(Ok(child))
+            }
+            Err(e) => {
+                self.pos = self.end;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// A traversal over a `Read + Seek` reader that seeks over the values
+///
+/// _Note: Headers are read with a call to the reader per byte; wrap unbuffered readers into a
+/// `std::io::BufReader`._
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+pub struct SeekTraversal<R: Read + Seek> {
+    reader: R,
+    options: DecodeOptions,
+}
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+impl<R: Read + Seek> SeekTraversal<R> {
+    /// Creates a new traversal over `reader`
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, DecodeOptions::STRICT)
+    }
+    /// Creates a new traversal over `reader` that decodes according to `options`
+    pub fn with_options(reader: R, options: DecodeOptions) -> Self {
+        Self { reader, options }
+    }
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the node at `offset`
+    ///
+    /// _Note: Only the header is read, so the node may be truncated._
+    pub fn node_at(&mut self, offset: u64) -> io::Result<Node> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut source = IoSource::new(&mut self.reader);
+        let header = Header::decode_from_source_with(&mut source, self.options);
+        Ok(Node { offset, header: source.io_result(header)? })
+    }
+    /// The node at the beginning of the reader
+    pub fn root(&mut self) -> io::Result<Node> {
+        self.node_at(0)
+    }
+    /// An iterator over the direct children of `node`
+    pub fn children(&mut self, node: &Node) -> io::Result<SeekChildren<'_, R>> {
+        Ok(SeekChildren { pos: node.value_offset()?, end: node.end()?, traversal: self })
+    }
+    /// Reads the subtree of `node` into `buf` and returns it as object
+    ///
+    /// _Note: `buf` is cleared before reading; the node is checked against the length of the reader
+    /// before any memory is allocated._
+    pub fn object<'a>(&mut self, node: &Node, buf: &'a mut Vec<u8>) -> io::Result<DerObject<'a>> {
+        if node.end()? > self.reader.seek(SeekFrom::End(0))? {
+            return Err(eio!("The node is truncated").into());
+        }
+
+        let len = node.header.object_len()?;
+        buf.clear();
+        buf.resize(len, 0);
+
+        self.reader.seek(SeekFrom::Start(node.offset))?;
+        self.reader.read_exact(buf)?;
+        Ok(DerObject::decode_with(buf, self.options)?)
+    }
+}
+
+/// An iterator over the direct children of a node within a reader
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+pub struct SeekChildren<'t, R: Read + Seek> {
+    traversal: &'t mut SeekTraversal<R>,
+    pos: u64,
+    end: u64,
+}
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+impl<'t, R: Read + Seek> Iterator for SeekChildren<'t, R> {
+    type Item = io::Result<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+
+        // Stop after the first error
+        let child = self.traversal.node_at(self.pos).and_then(|child| match child.end()? {
+            end if end <= self.end => Ok((child, end)),
+            _ => Err(einval!("The child exceeds its parent").into()),
+        });
+        match child {
+            Ok((child, end)) => {
+                self.pos = end;
+                Some(Ok(child))
+            }
+            Err(e) => {
+                self.pos = self.end;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
-use crate::{Asn1DerError, DerObject, Sink};
+use crate::typed::{DerDecodable, DerEncodable, DerTagged, DerTypeView};
+use crate::{Asn1DerError, DecodeOptions, DerObject, Sink};
 
 /// An ASN.1-DER boolean type view
 #[derive(Copy, Clone)]
 pub struct Boolean<'a> {
     object: DerObject<'a>,
 }
+owned_view!(BooleanBuf, Boolean, "boolean");
 impl<'a> Boolean<'a> {
     /// Writes a new boolean object with `value` into `sink` and returns a type view over it
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -21,7 +22,8 @@ impl<'a> Boolean<'a> {
         match self.object.value() {
             b"\x00" => false,
             // #implicit_validation: Since we validate this value at `load`, the only possible value
-            // here is `b"\xff"` unless the underlying object has been modified in an invalid way
+            // here is `b"\xff"` (or any other non-zero byte if loaded with `allow_ber_booleans`)
+            // unless the underlying object has been modified in an invalid way
             _ => true,
         }
     }
@@ -33,12 +35,13 @@ impl<'a> Boolean<'a> {
             true => b"\xff".as_ref(),
             false => b"\x00".as_ref(),
         };
-        DerObject::write(Self::TAG, value.len(), &mut value.iter(), sink).propagate(e!("Failed to write boolean"))
+        DerObject::write_slice(Self::TAG, value, sink).propagate(e!("Failed to write boolean"))
     }
 }
-impl<'a> DerTypeView<'a> for Boolean<'a> {
+impl<'a> DerTagged for Boolean<'a> {
     const TAG: u8 = b'\x01';
-
+}
+impl<'a> DerTypeView<'a> for Boolean<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -47,9 +50,14 @@ impl<'a> DerTypeView<'a> for Boolean<'a> {
 impl<'a> DerDecodable<'a> for Boolean<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
         match object.value() {
             _ if object.tag() != Self::TAG => Err(einval!("DER object is not a boolean"))?,
             b"\x00" | b"\xff" => Ok(Self { object }),
+            [_] if options.allow_ber_booleans => Ok(Self { object }),
             _ => Err(einval!("DER object is not a valid boolean")),
         }
     }
@@ -59,12 +67,20 @@ impl<'a> DerEncodable for Boolean<'a> {
     fn encode<U: Sink>(&self, sink: &mut U) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode boolean"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
 }
 
 impl<'a> DerDecodable<'a> for bool {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
-        let boolean = Boolean::load(object).propagate(e!("Failed to load boolean"))?;
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let boolean = Boolean::load_with(object, options).propagate(e!("Failed to load boolean"))?;
         Ok(boolean.get())
     }
 }
@@ -73,4 +89,11 @@ impl DerEncodable for bool {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         Boolean::write(*self, sink).propagate(e!("Failed to encode boolean"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Ok(3)
+    }
+}
+impl DerTagged for bool {
+    const TAG: u8 = Boolean::TAG;
 }
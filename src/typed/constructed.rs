@@ -0,0 +1,177 @@
+use crate::error::ErrorChain;
+use crate::typed::{CountingSink, DerEncodable, DerTagged, Sequence};
+use crate::{der, Asn1DerError, DerObject, ReverseSink, Sink};
+
+/// A trait for the list of subobjects that make up the value of a constructed object
+///
+/// _Note: This trait is implemented for `()` (no subobjects) and for `(A, B)`, where `A` is the
+/// list of preceding subobjects and `B` is the next subobject. You usually don't implement this
+/// trait yourself but build the list via [`ConstructedWriter::push`] or derive it._
+pub trait DerContents {
+    /// Encodes the subobjects into `sink`
+    fn encode_contents<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError>;
+    /// Computes the total length of the encoded subobjects
+    ///
+    /// _Note: The default implementation encodes the subobjects into a counting sink._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn contents_len(&self) -> Result<usize, Asn1DerError> {
+        let mut ctr = CountingSink(0);
+        self.encode_contents(&mut ctr).propagate(e!("Failed to size subobjects"))?;
+        Ok(ctr.0)
+    }
+    /// Encodes the subobjects in front of the bytes already written into `sink`
+    ///
+    /// _Note: The default implementation reserves `contents_len` bytes and encodes the subobjects
+    /// into them._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        let len = self.contents_len().propagate(e!("Failed to size subobjects"))?;
+        let mut reserved = sink.reserve(len).propagate(e!("Failed to reserve subobjects"))?.iter_mut();
+        self.encode_contents(&mut reserved).propagate(e!("Failed to encode subobjects"))?;
+        match reserved.len() {
+            0 => Ok(()),
+            _ => Err(eother!("The subobjects are shorter than their computed length")),
+        }
+    }
+}
+impl<C: DerContents> DerContents for &C {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        (*self).encode_contents(sink)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn contents_len(&self) -> Result<usize, Asn1DerError> {
+        (*self).contents_len()
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        (*self).encode_contents_reverse(sink)
+    }
+}
+impl DerContents for () {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents<S: Sink>(&self, _sink: &mut S) -> Result<(), Asn1DerError> {
+        Ok(())
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn contents_len(&self) -> Result<usize, Asn1DerError> {
+        Ok(0)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents_reverse<R: ReverseSink>(&self, _sink: &mut R) -> Result<(), Asn1DerError> {
+        Ok(())
+    }
+}
+impl<A: DerContents, B: DerEncodable> DerContents for (A, B) {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        self.0.encode_contents(sink)?;
+        self.1.encode(sink).propagate(e!("Failed to encode subobject"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn contents_len(&self) -> Result<usize, Asn1DerError> {
+        let len = self.0.contents_len()?;
+        let next = self.1.encoded_len().propagate(e!("Failed to size subobject"))?;
+        match len.checked_add(next) {
+            Some(len) => Ok(len),
+            None => Err(eunsupported!("The object length would exceed `usize::max_value()`")),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_contents_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        self.1.encode_reverse(sink).propagate(e!("Failed to encode subobject"))?;
+        self.0.encode_contents_reverse(sink)
+    }
+}
+
+/// A builder for constructed objects (e.g. a `SEQUENCE`) with heterogeneous subobjects
+///
+/// Unlike [`Sequence::write`], the subobjects don't need to share a common type: every
+/// `DerEncodable` can be pushed, including raw `DerObject`s and nested `ConstructedWriter`s. The
+/// subobjects are stored inline, so the builder works without dynamic memory allocation.
+///
+/// ```rust
+/// # use asn1_der::{ DerObject, Sink, typed::{ ConstructedWriter, DerEncodable } };
+/// // SEQUENCE { version INTEGER, name UTF8String, extensions [0] EXPLICIT SEQUENCE { BOOLEAN } }
+/// let name = DerObject::decode(b"\x0c\x04test").unwrap();
+/// let extensions = ConstructedWriter::sequence().push(true);
+/// let writer = ConstructedWriter::sequence().push(2u8).push(name).push(ConstructedWriter::new(0xa0).push(extensions));
+///
+/// let (mut buf, mut len) = ([0; 32], 0);
+/// writer.encode(&mut buf.iter_mut().counting_sink(&mut len)).unwrap();
+/// assert_eq!(&buf[..len], b"\x30\x10\x02\x01\x02\x0c\x04test\xa0\x05\x30\x03\x01\x01\xff");
+/// ```
+#[derive(Copy, Clone)]
+pub struct ConstructedWriter<C = ()> {
+    tag: u8,
+    contents: C,
+}
+impl ConstructedWriter {
+    /// Creates a new empty builder for a constructed object with `tag`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(tag: u8) -> Self {
+        Self { tag, contents: () }
+    }
+    /// Creates a new empty builder for a `SEQUENCE`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn sequence() -> Self {
+        Self::new(Sequence::TAG)
+    }
+}
+impl<C: DerContents> ConstructedWriter<C> {
+    /// Creates a new builder for a constructed object with `tag` and `contents` as subobjects
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn with_contents(tag: u8, contents: C) -> Self {
+        Self { tag, contents }
+    }
+
+    /// Appends `obj` as next subobject
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn push<T: DerEncodable>(self, obj: T) -> ConstructedWriter<(C, T)> {
+        ConstructedWriter { tag: self.tag, contents: (self.contents, obj) }
+    }
+
+    /// Writes the constructed object into `sink` and returns a view over it
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn finish<'a, S: Sink + Into<&'a [u8]>>(&self, mut sink: S) -> Result<DerObject<'a>, Asn1DerError> {
+        self.write(&mut sink).propagate(e!("Failed to construct object"))?;
+        DerObject::decode(sink.into()).propagate(e!("Failed to load constructed object"))
+    }
+    /// Writes the constructed object into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        // Compute the total length
+        let len = self.contents.contents_len().propagate(e!("Failed to size subobjects"))?;
+
+        // Encode the object by hand
+        sink.write(self.tag).propagate(e!("Failed to write tag"))?;
+        der::length::encode(len, sink).propagate(e!("Failed to encode length"))?;
+        self.contents.encode_contents(sink).propagate(e!("Failed to encode subobjects"))
+    }
+    /// Writes the constructed object back to front in front of the bytes already written into
+    /// `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        let written = sink.written_mut().len();
+        self.contents.encode_contents_reverse(sink).propagate(e!("Failed to encode subobjects"))?;
+
+        // #implicit_validation: the sink only grows, so the subtraction cannot underflow
+        let len = sink.written_mut().len().saturating_sub(written);
+        DerObject::write_header_reverse(self.tag, len, sink).propagate(e!("Failed to write header"))
+    }
+}
+impl<C: DerContents> DerEncodable for ConstructedWriter<C> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        self.write(sink).propagate(e!("Failed to encode constructed object"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        let len = self.contents.contents_len().propagate(e!("Failed to size subobjects"))?;
+        DerObject::tlv_len(len)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        self.write_reverse(sink).propagate(e!("Failed to encode constructed object"))
+    }
+}
@@ -0,0 +1,132 @@
+use crate::error::ErrorChain;
+use crate::typed::{DerDecodable, DerEncodable, DerTagged};
+use crate::{Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink};
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+
+/// A provider for the `DEFAULT` value of a component of type `T`
+pub trait DefaultValue<T> {
+    /// The `DEFAULT` value
+    fn default_value() -> T;
+}
+
+/// Uses `T::default()` as `DEFAULT` value (e.g. `0` for `INTEGER DEFAULT 0` or `false` for
+/// `BOOLEAN DEFAULT FALSE`)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TypeDefault;
+impl<T: Default> DefaultValue<T> for TypeDefault {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn default_value() -> T {
+        T::default()
+    }
+}
+
+/// A component with a `DEFAULT` value that is provided by `D` (`T DEFAULT ...`)
+///
+/// As required by DER, a value that is equal to the default value is omitted during encoding (i.e.
+/// nothing is written) and an explicitly encoded default value is rejected during decoding. An absent
+/// component is read via [`SequenceReader::read_defaulted`](crate::typed::SequenceReader::read_defaulted).
+pub struct Defaulted<T, D = TypeDefault> {
+    value: T,
+    default: PhantomData<D>,
+}
+impl<T, D> Defaulted<T, D> {
+    /// Creates a new defaulted component with `value`
+    pub const fn new(value: T) -> Self {
+        Self { value, default: PhantomData }
+    }
+
+    /// The value
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+    /// Returns the value
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+impl<T: PartialEq, D: DefaultValue<T>> Defaulted<T, D> {
+    /// Whether the value is equal to the `DEFAULT` value and thus omitted during encoding
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn is_default(&self) -> bool {
+        self.value == D::default_value()
+    }
+}
+impl<T, D: DefaultValue<T>> Default for Defaulted<T, D> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn default() -> Self {
+        Self::new(D::default_value())
+    }
+}
+impl<T, D> From<T> for Defaulted<T, D> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+impl<T: Clone, D> Clone for Defaulted<T, D> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+impl<T: Copy, D> Copy for Defaulted<T, D> {}
+impl<T: Debug, D> Debug for Defaulted<T, D> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Defaulted").field(&self.value).finish()
+    }
+}
+impl<T: PartialEq, D> PartialEq for Defaulted<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+impl<T: Eq, D> Eq for Defaulted<T, D> {}
+impl<T: DerTagged, D> DerTagged for Defaulted<T, D> {
+    const TAG: u8 = T::TAG;
+}
+impl<'a, T: DerDecodable<'a> + PartialEq, D: DefaultValue<T>> DerDecodable<'a> for Defaulted<T, D> {
+    /// Loads the object as `Self`
+    ///
+    /// _Note: Since the object is present, it must not encode the default value._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let this = Self::new(T::load_with(object, options).propagate(e!("Failed to load defaulted object"))?);
+        match this.is_default() {
+            true => Err(einval!("DER object explicitly encodes its DEFAULT value")),
+            false => Ok(this),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.value.allocated_len()
+    }
+}
+impl<T: DerEncodable + PartialEq, D: DefaultValue<T>> DerEncodable for Defaulted<T, D> {
+    /// Encodes `self` into `sink` if it is not equal to the default value
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        match self.is_default() {
+            true => Ok(()),
+            false => self.value.encode(sink).propagate(e!("Failed to encode defaulted object")),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        match self.is_default() {
+            true => Ok(0),
+            false => self.value.encoded_len(),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        match self.is_default() {
+            true => Ok(()),
+            false => self.value.encode_reverse(sink).propagate(e!("Failed to encode defaulted object")),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
-use crate::{Asn1DerError, DerObject, Sink};
+use crate::typed::{DerDecodable, DerEncodable, DerTagged, DerTypeView};
+use crate::{der, Asn1DerError, DecodeOptions, DerObject, Sink};
 use core::mem;
 
 /// An ASN.1-DER integer view
@@ -8,6 +8,7 @@ use core::mem;
 pub struct Integer<'a> {
     object: DerObject<'a>,
 }
+owned_view!(IntegerBuf, Integer, "integer");
 impl<'a> Integer<'a> {
     /// Writes a new integer object with the big-endian encoded `be_value` into `sink` and returns a
     /// type view over it
@@ -35,16 +36,20 @@ impl<'a> Integer<'a> {
     }
     /// Get the number bytes
     ///
+    /// _Note: Redundant leading bytes of an integer that has been loaded with
+    /// `allow_non_minimal_integers` are stripped off as well._
+    ///
     /// __Important: Any leading zero-byte that might indicate a positive number is stripped off.
     /// This means that a return value of `0b1111_1111` can be either `255` or `-1` depending on
     /// whether the number is negative or not. Use `is_negative` to determine the correct sign.__
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn get_numbytes(&self) -> &[u8] {
         let slice = self.object.value();
-        match slice.len() {
-            len if len >= 1 && slice[0] == 0 => &slice[1..],
-            _ => slice,
-        }
+        let to_skip = match self.is_negative() {
+            true => slice.windows(2).take_while(|pair| matches!(pair, [0xff, next] if next & 0b1000_0000 != 0)).count(),
+            false => slice.iter().take_while(|b| **b == 0).count(),
+        };
+        slice.get(to_skip..).unwrap_or_default()
     }
 
     /// Copies the num bytes into `buf` if they can fit
@@ -76,6 +81,26 @@ impl<'a> Integer<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     #[inline(always)]
     pub fn write<S: Sink>(value: &[u8], is_negative: bool, sink: &mut S) -> Result<(), Asn1DerError> {
+        let (to_skip, value_prefix, len) = Self::layout(value, is_negative)?;
+
+        // Encode integer
+        sink.write(Self::TAG).propagate(e!("Failed to write tag"))?;
+        der::length::encode(len, sink).propagate(e!("Failed to write length"))?;
+        sink.write_all(value_prefix).propagate(e!("Failed to write integer"))?;
+        sink.write_all(value.get(to_skip..).unwrap_or_default()).propagate(e!("Failed to write integer"))
+    }
+    /// Computes the length of the DER-object that `write` creates for `value`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_len(value: &[u8], is_negative: bool) -> Result<usize, Asn1DerError> {
+        let (_, _, len) = Self::layout(value, is_negative)?;
+        DerObject::tlv_len(len)
+    }
+
+    /// Computes the amount of leading zero bytes to skip, the necessary prefix and the value length
+    /// for `value`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    #[inline(always)]
+    fn layout(value: &[u8], is_negative: bool) -> Result<(usize, &'static [u8], usize), Asn1DerError> {
         // Determine the amount leading zero bytes to skip
         let to_skip = value.iter().take_while(|b| **b == 0).count();
         let len = value.iter().skip(to_skip).count();
@@ -86,19 +111,16 @@ impl<'a> Integer<'a> {
             Some(first) if first & 0b1000_0000 != 0 && !is_negative => b"\x00".as_ref(),
             _ => b"".as_ref(),
         };
-        let len = match len.checked_add(value_prefix.len()) {
-            Some(len) => len,
-            None => Err(eunsupported!("The number length would exceed `usize::max_value()`"))?,
-        };
-
-        // Encode integer
-        let mut value = value_prefix.iter().chain(value.iter().skip(to_skip));
-        DerObject::write(Self::TAG, len, &mut value, sink).propagate(e!("Failed to write integer"))
+        match len.checked_add(value_prefix.len()) {
+            Some(len) => Ok((to_skip, value_prefix, len)),
+            None => Err(eunsupported!("The number length would exceed `usize::max_value()`")),
+        }
     }
 }
-impl<'a> DerTypeView<'a> for Integer<'a> {
+impl<'a> DerTagged for Integer<'a> {
     const TAG: u8 = b'\x02';
-
+}
+impl<'a> DerTypeView<'a> for Integer<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -107,9 +129,14 @@ impl<'a> DerTypeView<'a> for Integer<'a> {
 impl<'a> DerDecodable<'a> for Integer<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
         match object.value() {
             _ if object.tag() != Self::TAG => Err(einval!("DER object is not an integer"))?,
-            value if value.is_empty() => Err(einval!("DER object is not a valid integer"))?,
+            [] => Err(einval!("DER object is not a valid integer"))?,
+            _ if options.allow_non_minimal_integers => Ok(Self { object }),
             value if value.len() >= 2 && value[0] == b'\x00' && value[1] & 0b1000_0000 == 0 => {
                 Err(einval!("DER object is not a valid integer"))
             }
@@ -126,6 +153,10 @@ impl<'a> DerEncodable for Integer<'a> {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode integer"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
 }
 
 /// Implements `DerCodable`
@@ -134,8 +165,12 @@ macro_rules! impl_dercodable {
 		impl<'a> DerDecodable<'a> for $num {
 			#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
 			fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+				Self::load_with(object, DecodeOptions::STRICT)
+			}
+			#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+			fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
 				// Load integer
-				let integer = Integer::load(object).propagate(e!("Failed to load integer"))?;
+				let integer = Integer::load_with(object, options).propagate(e!("Failed to load integer"))?;
 				let buf = integer.copy_numbytes([0; mem::size_of::<Self>()])
 					.propagate(e!("The numeric value is too large"))?;
 
@@ -152,6 +187,13 @@ macro_rules! impl_dercodable {
 			fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
 				Integer::write(&self.to_be_bytes(), false, sink)
 			}
+			#[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+			fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+				Integer::write_len(&self.to_be_bytes(), false)
+			}
+		}
+		impl DerTagged for $num {
+			const TAG: u8 = Integer::TAG;
 		}
 	};
 	(unsigned: $($num:ty),+) => ($( impl_dercodable!(unsigned: $num); )+);
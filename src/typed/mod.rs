@@ -1,28 +1,173 @@
 //! Some traits to de-/encode DER objects via type-specific zero-copy views as well as direct
 //! de-/encode implementations for some native Rust types
 
+/// Implements an owned variant `$owned` of the type view `$view` together with `$view::to_buf`
+macro_rules! owned_view {
+    ($owned:ident, $view:ident, $desc:literal) => {
+        #[doc = concat!("An owned ", $desc, " that can outlive the decoded data")]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+        pub struct $owned {
+            object: crate::DerObjectBuf,
+        }
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        impl $owned {
+            /// A type view over `self`
+            pub fn view(&self) -> $view<'_> {
+                $view { object: self.object.object() }
+            }
+            /// The underlying object
+            pub fn object(&self) -> &crate::DerObjectBuf {
+                &self.object
+            }
+            /// Returns the underlying object
+            pub fn into_object(self) -> crate::DerObjectBuf {
+                self.object
+            }
+        }
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        impl<'a> $view<'a> {
+            #[doc = concat!("Copies the ", $desc, " into an owned `", stringify!($owned), "`")]
+            pub fn to_buf(&self) -> $owned {
+                $owned { object: self.object.to_buf() }
+            }
+        }
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        impl crate::typed::DerTagged for $owned {
+            const TAG: u8 = <$view as crate::typed::DerTagged>::TAG;
+        }
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        impl<'a> crate::typed::DerDecodable<'a> for $owned {
+            fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+                let view = $view::load(object).propagate(e!(concat!("Failed to load ", $desc)))?;
+                Ok(view.to_buf())
+            }
+            fn load_with(object: DerObject<'a>, options: crate::DecodeOptions) -> Result<Self, Asn1DerError> {
+                let view = $view::load_with(object, options).propagate(e!(concat!("Failed to load ", $desc)))?;
+                options.limits.check_alloc(object.raw().len()).propagate(e!("Failed to copy object"))?;
+                Ok(view.to_buf())
+            }
+            fn allocated_len(&self) -> usize {
+                self.object.raw().len()
+            }
+        }
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        impl crate::typed::DerEncodable for $owned {
+            fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+                self.view().encode(sink)
+            }
+            fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+                self.view().encoded_len()
+            }
+        }
+    };
+}
+
 mod boolean;
+mod constructed;
+mod defaulted;
 mod integer;
 mod null;
 mod octet_string;
+mod optional;
 mod sequence;
+mod tagged;
 mod utf8_string;
 
+use crate::cer::CerSink;
 use crate::error::ErrorChain;
 pub use crate::typed::boolean::Boolean;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::typed::boolean::BooleanBuf;
+pub use crate::typed::constructed::{ConstructedWriter, DerContents};
+pub use crate::typed::defaulted::{DefaultValue, Defaulted, TypeDefault};
 pub use crate::typed::integer::Integer;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::typed::integer::IntegerBuf;
 pub use crate::typed::null::Null;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::typed::null::NullBuf;
 pub use crate::typed::octet_string::OctetString;
-pub use crate::typed::sequence::Sequence;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::typed::octet_string::OctetStringBuf;
+pub use crate::typed::optional::Optional;
+pub use crate::typed::sequence::{Sequence, SequenceReader};
+pub use crate::typed::tagged::{Explicit, Implicit};
 pub use crate::typed::utf8_string::Utf8String;
-use crate::{Asn1DerError, DerObject, Sink, Source};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use crate::typed::utf8_string::Utf8StringBuf;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use crate::DerObjectBuf;
+#[cfg(feature = "std")]
+use crate::IoSink;
+use crate::{Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink, Source};
 #[cfg(all(feature = "std", not(feature = "no_panic")))]
-pub use sequence::SequenceVec;
+use crate::{IoSource, VecBacking};
+#[cfg(feature = "derive")]
+pub use asn1_der_derive::{DerDecodable, DerEncodable};
+#[cfg(feature = "heapless")]
+pub use sequence::HeaplessSequence;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+pub use sequence::{SequenceBuf, SequenceVec};
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+use std::io::Read;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
 
-/// A trait for DER type views
-pub trait DerTypeView<'a>: Sized {
+/// A counting sink that swallows each element and increments a counter
+pub(crate) struct CountingSink(pub usize);
+impl Sink for CountingSink {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, _e: u8) -> Result<(), Asn1DerError> {
+        match self.0.checked_add(1) {
+            Some(next) => {
+                self.0 = next;
+                Ok(())
+            }
+            None => Err(eunsupported!("Cannot write more than `usize::max_value()` bytes")),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Asn1DerError> {
+        match self.0.checked_add(bytes.len()) {
+            Some(next) => {
+                self.0 = next;
+                Ok(())
+            }
+            None => Err(eunsupported!("Cannot write more than `usize::max_value()` bytes")),
+        }
+    }
+}
+
+/// A trait for types that are always encoded with the same tag
+pub trait DerTagged {
     /// The tag for this type
     const TAG: u8;
+}
+impl<T: DerTagged> DerTagged for &T {
+    const TAG: u8 = T::TAG;
+}
+
+/// A trait for types that can be encoded with one of several tags (e.g. an ASN.1 `CHOICE`)
+///
+/// This is the counterpart to `DerTagged` for types where the incoming tag selects the
+/// alternative to load.
+pub trait DerChoice {
+    /// Checks whether an object with `tag` can be loaded as `Self`
+    fn matches_tag(tag: u8) -> bool;
+}
+impl<T: DerChoice> DerChoice for &T {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn matches_tag(tag: u8) -> bool {
+        T::matches_tag(tag)
+    }
+}
+
+/// A trait for DER type views
+///
+/// _Note: The tag of a view is its `DerTagged::TAG`, so that a view can be used wherever a tagged
+/// type is expected._
+pub trait DerTypeView<'a>: DerTagged + Sized {
     /// Provides raw access to the underlying `DerObject`
     fn object(&self) -> DerObject<'a>;
 }
@@ -31,6 +176,24 @@ pub trait DerTypeView<'a>: Sized {
 pub trait DerDecodable<'a>: Sized {
     /// Loads `object` as `Self`
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError>;
+    /// Loads `object` as `Self` according to `options`
+    ///
+    /// _Note: The default implementation ignores `options` and calls `load`; types with relaxable
+    /// validation rules or subobjects override this._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let _ = options;
+        Self::load(object)
+    }
+    /// The amount of bytes that have been allocated to load `self` (including its subobjects)
+    ///
+    /// _Note: Constructed types draw this amount from `DecodeLimits::max_alloc` after loading a
+    /// subobject; the default implementation returns `0`, so types that allocate must override
+    /// this._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        0
+    }
     /// Decodes an object as `Self`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn decode(raw: &'a [u8]) -> Result<Self, Asn1DerError> {
@@ -42,6 +205,12 @@ pub trait DerDecodable<'a>: Sized {
         let object = DerObject::decode_at(raw, header_start).propagate(e!("Failed to decode object"))?;
         Self::load(object).propagate(e!("Failed to load object"))
     }
+    /// Decodes an object as `Self` according to `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn decode_with(raw: &'a [u8], options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let object = DerObject::decode_with(raw, options).propagate(e!("Failed to decode object"))?;
+        Self::load_with(object, options).propagate(e!("Failed to load object"))
+    }
     /// Reads an object from `source` by parsing the length field and copying the necessary bytes
     /// into `sink` and decoding it from `sink`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -49,6 +218,36 @@ pub trait DerDecodable<'a>: Sized {
         let object = DerObject::decode_from_source(source, sink).propagate(e!("Failed to decode object"))?;
         Self::load(object).propagate(e!("Failed to load object"))
     }
+    /// Reads an object from `source` according to `options` by parsing the length field and copying
+    /// the necessary bytes into `sink` and decoding it from `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn decode_from_source_with<A: Source, B: Sink + Into<&'a [u8]>>(
+        source: &mut A,
+        sink: B,
+        options: DecodeOptions,
+    ) -> Result<Self, Asn1DerError> {
+        let object =
+            DerObject::decode_from_source_with(source, sink, options).propagate(e!("Failed to decode object"))?;
+        Self::load_with(object, options).propagate(e!("Failed to load object"))
+    }
+    /// Reads an object from `reader` into `buf` and decodes it as `Self`
+    ///
+    /// _Note: `buf` is cleared before reading; exactly one object is read from `reader`, so that
+    /// subsequent objects can be read from the same reader._
+    #[cfg(all(feature = "std", not(feature = "no_panic")))]
+    fn decode_from_reader<R: Read>(reader: R, buf: &'a mut Vec<u8>) -> io::Result<Self> {
+        Self::decode_from_reader_with(reader, buf, DecodeOptions::STRICT)
+    }
+    /// Reads an object from `reader` into `buf` and decodes it as `Self` according to `options`
+    ///
+    /// _Note: The object length is checked against `options.limits` before `buf` grows._
+    #[cfg(all(feature = "std", not(feature = "no_panic")))]
+    fn decode_from_reader_with<R: Read>(reader: R, buf: &'a mut Vec<u8>, options: DecodeOptions) -> io::Result<Self> {
+        buf.clear();
+        let mut source = IoSource::new(reader);
+        let result = Self::decode_from_source_with(&mut source, VecBacking(buf), options);
+        source.io_result(result)
+    }
 }
 impl<'a> DerDecodable<'a> for DerObject<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -56,11 +255,52 @@ impl<'a> DerDecodable<'a> for DerObject<'a> {
         Ok(object)
     }
 }
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a, B: AsRef<[u8]>> DerDecodable<'a> for DerObjectBuf<B>
+where
+    DerObjectBuf<B>: From<DerObject<'a>>,
+{
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Ok(Self::from(object))
+    }
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        options.limits.check_alloc(object.raw().len()).propagate(e!("Failed to copy object"))?;
+        Ok(Self::from(object))
+    }
+    fn allocated_len(&self) -> usize {
+        self.raw().len()
+    }
+}
 
 /// A trait for DER encodable types
 pub trait DerEncodable: Sized {
     /// Encodes `self` into `sink`
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError>;
+    /// Computes the exact length of the encoding of `self`
+    ///
+    /// _Note: The default implementation encodes `self` into a counting sink; all built-in types
+    /// compute the length directly._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        let mut ctr = CountingSink(0);
+        self.encode(&mut ctr).propagate(e!("Failed to size object"))?;
+        Ok(ctr.0)
+    }
+    /// Encodes `self` in front of the bytes already written into `sink`
+    ///
+    /// _Note: The default implementation reserves `encoded_len` bytes and encodes `self` into them;
+    /// constructed built-in types encode their subobjects back to front instead, so that every
+    /// length is known when it is written._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        let len = self.encoded_len().propagate(e!("Failed to size object"))?;
+        let mut reserved = sink.reserve(len).propagate(e!("Failed to reserve object"))?.iter_mut();
+        self.encode(&mut reserved).propagate(e!("Failed to encode object"))?;
+        match reserved.len() {
+            0 => Ok(()),
+            _ => Err(eother!("The object is shorter than its computed length")),
+        }
+    }
 
     /// Creates an DER object from an encodable type
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -68,22 +308,86 @@ pub trait DerEncodable: Sized {
         self.encode(&mut sink).propagate(e!("Failed to encode object"))?;
         DerObject::decode(sink.into()).propagate("Failed to load constructed object")
     }
+    /// Encodes `self` back to front into `sink` and returns a view over the written bytes
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn der_object_reverse<'a, R: ReverseSink + Into<&'a [u8]>>(
+        &self,
+        mut sink: R,
+    ) -> Result<DerObject<'a>, Asn1DerError> {
+        self.encode_reverse(&mut sink).propagate(e!("Failed to encode object"))?;
+        DerObject::decode(sink.into()).propagate(e!("Failed to load constructed object"))
+    }
+    /// Encodes `self` as CER into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_cer<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        let mut sink = CerSink::new(sink);
+        self.encode(&mut sink).propagate(e!("Failed to encode object"))?;
+        sink.finish().propagate(e!("Failed to finish CER object"))?;
+        Ok(())
+    }
+    /// Encodes `self` into `writer`
+    ///
+    /// _Note: `writer` is not flushed._
+    #[cfg(feature = "std")]
+    fn encode_to_writer<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut sink = IoSink::new(writer);
+        let result = self.encode(&mut sink);
+        sink.io_result(result)
+    }
 }
 impl<'a> DerEncodable for DerObject<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         self.encode(sink).propagate(e!("Failed to encode object"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.value().len())
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<B: AsRef<[u8]>> DerEncodable for DerObjectBuf<B> {
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        self.object().encode(sink).propagate(e!("Failed to encode object"))
+    }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Ok(self.raw().len())
+    }
+}
+#[cfg(all(feature = "bytes", not(feature = "no_panic")))]
+impl DerEncodable for crate::SharedDerObject {
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        self.object().encode(sink).propagate(e!("Failed to encode object"))
+    }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Ok(self.raw().len())
+    }
 }
 impl<T: DerEncodable> DerEncodable for &T {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         (*self).encode(sink)
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        (*self).encoded_len()
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        (*self).encode_reverse(sink)
+    }
 }
 impl<T: DerEncodable> DerEncodable for &mut T {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         (*self as &T).encode(sink)
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        (*self as &T).encoded_len()
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        (*self as &T).encode_reverse(sink)
+    }
 }
@@ -1,12 +1,13 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
-use crate::{Asn1DerError, DerObject, Sink};
+use crate::typed::{DerDecodable, DerEncodable, DerTagged, DerTypeView};
+use crate::{Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink};
 
 /// An ASN.1-DER null object view
 #[derive(Copy, Clone)]
 pub struct Null<'a> {
     object: DerObject<'a>,
 }
+owned_view!(NullBuf, Null, "null object");
 impl<'a> Null<'a> {
     /// Writes a new null object into `sink` and returns a type view over it
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -19,12 +20,13 @@ impl<'a> Null<'a> {
     /// Writes a boolean `value` as DER-object to `sink`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn write<S: Sink>(sink: &mut S) -> Result<(), Asn1DerError> {
-        DerObject::write(Self::TAG, 0, &mut b"".iter(), sink).propagate(e!("Failed to write null object"))
+        DerObject::write_slice(Self::TAG, b"", sink).propagate(e!("Failed to write null object"))
     }
 }
-impl<'a> DerTypeView<'a> for Null<'a> {
+impl<'a> DerTagged for Null<'a> {
     const TAG: u8 = b'\x05';
-
+}
+impl<'a> DerTypeView<'a> for Null<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -46,6 +48,10 @@ impl<'a> DerEncodable for Null<'a> {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode null object"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
 }
 
 impl<'a> DerDecodable<'a> for () {
@@ -60,22 +66,41 @@ impl DerEncodable for () {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         Null::write(sink).propagate(e!("Failed to encode null object"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Ok(2)
+    }
+}
+impl DerTagged for () {
+    const TAG: u8 = Null::TAG;
 }
 
+/// `T OR NULL` where `None` is encoded as `NULL`
+///
+/// _Note: This is not an ASN.1 `OPTIONAL` component, which is omitted if absent; use `Optional<T>`
+/// for that._
 impl<'a, T: DerDecodable<'a>> DerDecodable<'a> for Option<T> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
         match object.tag() {
             Null::TAG => {
                 Null::load(object).propagate(e!("Failed to load null object"))?;
                 Ok(None)
             }
             _ => {
-                let object = T::load(object).propagate(e!("Failed to load object"))?;
+                let object = T::load_with(object, options).propagate(e!("Failed to load object"))?;
                 Ok(Some(object))
             }
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.as_ref().map_or(0, T::allocated_len)
+    }
 }
 impl<T: DerEncodable> DerEncodable for Option<T> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -86,4 +111,18 @@ impl<T: DerEncodable> DerEncodable for Option<T> {
             None => Null::write(sink).propagate(e!("Failed to encode null object")),
         }
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        match self {
+            Some(object) => object.encoded_len(),
+            None => Ok(2),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        match self {
+            Some(object) => object.encode_reverse(sink).propagate(e!("Failed to encode object")),
+            None => DerObject::write_reverse(Null::TAG, b"", sink).propagate(e!("Failed to encode null object")),
+        }
+    }
 }
@@ -1,12 +1,17 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
+use crate::typed::{DerDecodable, DerEncodable, DerTagged, DerTypeView};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use crate::DecodeOptions;
 use crate::{Asn1DerError, DerObject, Sink};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use alloc::{borrow::Cow, vec::Vec};
 
 /// An ASN.1-DER octet string view
 #[derive(Copy, Clone)]
 pub struct OctetString<'a> {
     object: DerObject<'a>,
 }
+owned_view!(OctetStringBuf, OctetString, "octet string");
 impl<'a> OctetString<'a> {
     /// Writes a new octet string object with `value` into `sink` and returns a type view over it
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -24,12 +29,13 @@ impl<'a> OctetString<'a> {
     /// Writes an octet string `value` as DER-object to `sink`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn write<S: Sink>(value: &[u8], sink: &mut S) -> Result<(), Asn1DerError> {
-        DerObject::write(Self::TAG, value.len(), &mut value.iter(), sink).propagate(e!("Failed to write octet string"))
+        DerObject::write_slice(Self::TAG, value, sink).propagate(e!("Failed to write octet string"))
     }
 }
-impl<'a> DerTypeView<'a> for OctetString<'a> {
+impl<'a> DerTagged for OctetString<'a> {
     const TAG: u8 = b'\x04';
-
+}
+impl<'a> DerTypeView<'a> for OctetString<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -50,18 +56,102 @@ impl<'a> DerEncodable for OctetString<'a> {
     fn encode<U: Sink>(&self, sink: &mut U) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode octet string"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
 }
 
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
-impl<'a> DerDecodable<'a> for Vec<u8> {
+impl<'a> DerDecodable<'a> for &'a [u8] {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
         let octet_string = OctetString::load(object).propagate(e!("Failed to load octet string"))?;
+        Ok(octet_string.object().value())
+    }
+}
+impl DerEncodable for &[u8] {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        OctetString::write(self, sink).propagate(e!("Failed to encode octet string"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+impl DerTagged for &[u8] {
+    const TAG: u8 = OctetString::TAG;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> DerDecodable<'a> for Cow<'a, [u8]> {
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        let bytes = <&[u8]>::load(object).propagate(e!("Failed to load octet string"))?;
+        Ok(Cow::Borrowed(bytes))
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerEncodable for Cow<'_, [u8]> {
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        OctetString::write(self, sink).propagate(e!("Failed to encode octet string"))
+    }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerTagged for Cow<'_, [u8]> {
+    const TAG: u8 = OctetString::TAG;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> DerDecodable<'a> for Vec<u8> {
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let octet_string = OctetString::load_with(object, options).propagate(e!("Failed to load octet string"))?;
+        options.limits.check_alloc(octet_string.get().len()).propagate(e!("Failed to allocate octet string"))?;
         Ok(octet_string.get().to_vec())
     }
+    fn allocated_len(&self) -> usize {
+        self.len()
+    }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl DerEncodable for Vec<u8> {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         OctetString::write(self, sink).propagate(e!("Failed to encode octet string"))
     }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerTagged for Vec<u8> {
+    const TAG: u8 = OctetString::TAG;
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> DerDecodable<'a> for heapless::Vec<u8, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        let bytes = <&[u8]>::load(object).propagate(e!("Failed to load octet string"))?;
+        Self::from_slice(bytes).map_err(|_| eunsupported!("The octet string exceeds the capacity of the vector"))
+    }
+}
+#[cfg(feature = "heapless")]
+impl<const N: usize> DerEncodable for heapless::Vec<u8, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        OctetString::write(self, sink).propagate(e!("Failed to encode octet string"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(feature = "heapless")]
+impl<const N: usize> DerTagged for heapless::Vec<u8, N> {
+    const TAG: u8 = OctetString::TAG;
 }
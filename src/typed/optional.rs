@@ -0,0 +1,66 @@
+use crate::error::ErrorChain;
+use crate::typed::{DerDecodable, DerEncodable, DerTagged};
+use crate::{Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink};
+
+/// An `OPTIONAL` component (`T OPTIONAL`)
+///
+/// In contrast to `Option<T>`, which encodes `None` as `NULL` (i.e. `T OR NULL`), an absent
+/// component is omitted during encoding (i.e. nothing is written). An absent component is detected
+/// by peeking its tag via [`SequenceReader::read_optional`](crate::typed::SequenceReader::read_optional).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Optional<T>(pub Option<T>);
+impl<T> From<Option<T>> for Optional<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+impl<T> From<Optional<T>> for Option<T> {
+    fn from(value: Optional<T>) -> Self {
+        value.0
+    }
+}
+impl<T: DerTagged> DerTagged for Optional<T> {
+    const TAG: u8 = T::TAG;
+}
+impl<'a, T: DerDecodable<'a>> DerDecodable<'a> for Optional<T> {
+    /// Loads the object as `Self`
+    ///
+    /// _Note: Since the object is present, this always returns `Some`._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let value = T::load_with(object, options).propagate(e!("Failed to load optional object"))?;
+        Ok(Self(Some(value)))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.0.as_ref().map_or(0, T::allocated_len)
+    }
+}
+impl<T: DerEncodable> DerEncodable for Optional<T> {
+    /// Encodes `self` into `sink` if it is present
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        match &self.0 {
+            Some(value) => value.encode(sink).propagate(e!("Failed to encode optional object")),
+            None => Ok(()),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        match &self.0 {
+            Some(value) => value.encoded_len(),
+            None => Ok(0),
+        }
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        match &self.0 {
+            Some(value) => value.encode_reverse(sink).propagate(e!("Failed to encode optional object")),
+            None => Ok(()),
+        }
+    }
+}
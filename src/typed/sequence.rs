@@ -1,28 +1,21 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
-use crate::{der, Asn1DerError, DerObject, Sink};
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
-use core::ops::{Deref, DerefMut};
-
-/// A counting sink that swallows each element and increments a counter
-struct CountingSink(pub usize);
-impl Sink for CountingSink {
-    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
-    fn write(&mut self, _e: u8) -> Result<(), Asn1DerError> {
-        match self.0.checked_add(1) {
-            Some(next) => {
-                self.0 = next;
-                Ok(())
-            }
-            None => Err(eunsupported!("Cannot write more than `usize::max_value()` bytes")),
-        }
-    }
-}
+use crate::typed::{DefaultValue, Defaulted, DerDecodable, DerEncodable, DerTagged, DerTypeView};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use crate::DerObjectBuf;
+use crate::{der, Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 /// An ASN.1-DER sequence view
 #[derive(Copy, Clone)]
 pub struct Sequence<'a> {
     object: DerObject<'a>,
+    options: DecodeOptions,
 }
 impl<'a> Sequence<'a> {
     /// Writes a new sequence object with `objs` as subobjects into `sink` and returns a type view
@@ -31,7 +24,7 @@ impl<'a> Sequence<'a> {
     pub fn new<S: Sink + Into<&'a [u8]>, T: DerEncodable>(objs: &[T], mut sink: S) -> Result<Self, Asn1DerError> {
         Self::write(objs, &mut sink).propagate(e!("Failed to construct sequence"))?;
         let object = DerObject::decode(sink.into()).propagate(e!("Failed to load constructed sequence"))?;
-        Ok(Self { object })
+        Ok(Self { object, options: DecodeOptions::STRICT })
     }
 
     /// The amount of subelements in the sequence
@@ -71,10 +64,19 @@ impl<'a> Sequence<'a> {
     ///
     /// _Note: since there is no underlying index, the position of each subelement has to be
     /// recomputed every time. If you need the subobjects more than once, consider caching them._
+    ///
+    /// _Note: Each subobject may use the entire allocation budget of the sequence; use `reader` to
+    /// load several subobjects within a shared budget._
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn get_as<T: DerDecodable<'a>>(&self, n: usize) -> Result<T, Asn1DerError> {
         let object = self.get(n).propagate(e!("No subobject for given index"))?;
-        T::load(object).propagate(e!("Failed to load subobject"))
+        T::load_with(object, self.options).propagate(e!("Failed to load subobject"))
+    }
+
+    /// Creates a reader to sequentially load the subobjects
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn reader(&self) -> SequenceReader<'a> {
+        SequenceReader::with_options(self.object.value(), self.options)
     }
 
     /// Gets the subobject at `pos`
@@ -82,7 +84,8 @@ impl<'a> Sequence<'a> {
     fn subobject_at(&self, pos: &mut usize) -> Result<DerObject<'a>, Asn1DerError> {
         // Load object
         let sequence_value = self.object.value();
-        let object = DerObject::decode_at(sequence_value, *pos).propagate(e!("Failed to decode subobject"))?;
+        let object = DerObject::decode_at_with(sequence_value, *pos, self.options)
+            .propagate(e!("Failed to decode subobject"))?;
         let (object_header, object_value) = (object.header(), object.value());
 
         // #implicit_validation: since both slices are subslices of the same slice, their lengths
@@ -99,18 +102,47 @@ impl<'a> Sequence<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn write<S: Sink, T: DerEncodable>(objs: &[T], sink: &mut S) -> Result<(), Asn1DerError> {
         // Compute the total length
-        let mut ctr = CountingSink(0);
-        objs.iter().try_for_each(|o| o.encode(&mut ctr).propagate(e!("Failed to size subobject")))?;
+        let len = Self::contents_len(objs)?;
 
         // Encode the object by hand
         sink.write(Self::TAG).propagate(e!("Failed to write tag"))?;
-        der::length::encode(ctr.0, sink).propagate(e!("Failed to encode length"))?;
+        der::length::encode(len, sink).propagate(e!("Failed to encode length"))?;
         objs.iter().try_for_each(|o| o.encode(sink).propagate(e!("Failed to encode subobject")))
     }
+    /// Writes a sequence consisting of `objs` back to front in front of the bytes already written
+    /// into `sink`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_reverse<R: ReverseSink, T: DerEncodable>(objs: &[T], sink: &mut R) -> Result<(), Asn1DerError> {
+        let written = sink.written_mut().len();
+        objs.iter().rev().try_for_each(|o| o.encode_reverse(sink).propagate(e!("Failed to encode subobject")))?;
+
+        // #implicit_validation: the sink only grows, so the subtraction cannot underflow
+        let len = sink.written_mut().len().saturating_sub(written);
+        DerObject::write_header_reverse(Self::TAG, len, sink).propagate(e!("Failed to write header"))
+    }
+    /// Computes the length of the DER-object that `write` creates for `objs`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn write_len<T: DerEncodable>(objs: &[T]) -> Result<usize, Asn1DerError> {
+        let len = Self::contents_len(objs)?;
+        DerObject::tlv_len(len)
+    }
+
+    /// Computes the total length of the encoded `objs`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn contents_len<T: DerEncodable>(objs: &[T]) -> Result<usize, Asn1DerError> {
+        objs.iter().try_fold(0usize, |len, o| {
+            let o_len = o.encoded_len().propagate(e!("Failed to size subobject"))?;
+            match len.checked_add(o_len) {
+                Some(len) => Ok(len),
+                None => Err(eunsupported!("The sequence length would exceed `usize::max_value()`")),
+            }
+        })
+    }
 }
-impl<'a> DerTypeView<'a> for Sequence<'a> {
+impl<'a> DerTagged for Sequence<'a> {
     const TAG: u8 = b'\x30';
-
+}
+impl<'a> DerTypeView<'a> for Sequence<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -124,16 +156,27 @@ impl<'a> DerDecodable<'a> for Sequence<'a> {
     /// type-specific validation – only the tag-length constructions are validated._
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    /// Loads the `Sequence` according to `options`; the options for the next nesting level are
+    /// retained to access the subobjects
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
         // Validate the tag
+        let options = options.nested().propagate(e!("Failed to load sequence"))?;
         let this = match object.tag() {
-            Self::TAG => Self { object },
+            Self::TAG => Self { object, options },
             _ => Err(einval!("DER object is not a valid sequence"))?,
         };
 
         // Validate the subobjects
-        let (mut pos, total_len) = (0, this.object.value().len());
+        let (mut pos, mut count, total_len) = (0, 0usize, this.object.value().len());
         while pos < total_len {
             this.subobject_at(&mut pos).propagate(e!("Invalid subobject in sequence"))?;
+            // #implicit_validation: The counter can never overflow an usize because this would
+            // imply object lengths < 1
+            count = count.saturating_add(1);
+            options.limits.check_elements(count).propagate(e!("Too many subobjects in sequence"))?;
         }
         Ok(this)
     }
@@ -144,6 +187,178 @@ impl<'a> DerEncodable for Sequence<'a> {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode sequence"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
+}
+
+/// An owned sequence that can outlive the decoded data
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SequenceBuf {
+    object: DerObjectBuf,
+    options: DecodeOptions,
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl SequenceBuf {
+    /// A type view over `self`
+    pub fn view(&self) -> Sequence<'_> {
+        Sequence { object: self.object.object(), options: self.options }
+    }
+    /// The underlying object
+    pub fn object(&self) -> &DerObjectBuf {
+        &self.object
+    }
+    /// Returns the underlying object
+    pub fn into_object(self) -> DerObjectBuf {
+        self.object
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> Sequence<'a> {
+    /// Copies the sequence into an owned `SequenceBuf`; the options are retained
+    pub fn to_buf(&self) -> SequenceBuf {
+        SequenceBuf { object: self.object.to_buf(), options: self.options }
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerTagged for SequenceBuf {
+    const TAG: u8 = Sequence::TAG;
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> DerDecodable<'a> for SequenceBuf {
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        let sequence = Sequence::load(object).propagate(e!("Failed to load sequence"))?;
+        Ok(sequence.to_buf())
+    }
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let sequence = Sequence::load_with(object, options).propagate(e!("Failed to load sequence"))?;
+        options.limits.check_alloc(object.raw().len()).propagate(e!("Failed to copy sequence"))?;
+        Ok(sequence.to_buf())
+    }
+    fn allocated_len(&self) -> usize {
+        self.object.raw().len()
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerEncodable for SequenceBuf {
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        self.view().encode(sink)
+    }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        self.view().encoded_len()
+    }
+}
+
+/// A reader to sequentially load the subobjects of a constructed object (e.g. a `SEQUENCE`)
+///
+/// This is useful if the subobjects have different types, since each subobject can be loaded as
+/// a different `DerDecodable`. The allocations of all subobjects that are loaded via `read` are
+/// drawn from a shared `max_alloc` budget.
+#[derive(Copy, Clone)]
+pub struct SequenceReader<'a> {
+    value: &'a [u8],
+    pos: usize,
+    options: DecodeOptions,
+}
+impl<'a> SequenceReader<'a> {
+    /// Creates a new reader over `value` which contains the concatenated subobjects
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn new(value: &'a [u8]) -> Self {
+        Self::with_options(value, DecodeOptions::STRICT)
+    }
+    /// Creates a new reader over `value` which decodes and loads the subobjects according to
+    /// `options`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn with_options(value: &'a [u8], options: DecodeOptions) -> Self {
+        Self { value, pos: 0, options }
+    }
+
+    /// Whether all subobjects have been read
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.value.len()
+    }
+    /// Gets the next subobject without consuming it or `None` if all subobjects have been read
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn peek(&self) -> Result<Option<DerObject<'a>>, Asn1DerError> {
+        match self.is_empty() {
+            true => Ok(None),
+            false => {
+                let object = DerObject::decode_at_with(self.value, self.pos, self.options)
+                    .propagate(e!("Failed to decode subobject"))?;
+                Ok(Some(object))
+            }
+        }
+    }
+    /// Reads the next subobject
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn next_object(&mut self) -> Result<DerObject<'a>, Asn1DerError> {
+        let object = match self.peek().propagate(e!("Failed to read subobject"))? {
+            Some(object) => object,
+            None => Err(einval!("The sequence has no more subobjects"))?,
+        };
+
+        // #implicit_validation: since the object is a subslice of `value`, the position can never
+        // exceed `usize::max_value()`
+        self.pos = self.pos.saturating_add(object.raw().len());
+        Ok(object)
+    }
+    /// Reads the next subobject as `T`
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn read<T: DerDecodable<'a>>(&mut self) -> Result<T, Asn1DerError> {
+        let object = self.next_object().propagate(e!("Failed to read subobject"))?;
+        let value = T::load_with(object, self.options).propagate(e!("Failed to load subobject"))?;
+
+        // Draw the allocations of the subobject from the remaining budget
+        let limits = self.options.limits.allocate(value.allocated_len()).propagate(e!("Failed to load subobject"))?;
+        self.options.limits = limits;
+        Ok(value)
+    }
+    /// Reads the next subobject as `T` if it is tagged with `T::TAG` or returns `None` otherwise
+    ///
+    /// _Note: This reads an `OPTIONAL` component that is omitted if absent (see `Optional<T>`); use
+    /// `Optional::from` to wrap the result._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn read_optional<T: DerDecodable<'a> + DerTagged>(&mut self) -> Result<Option<T>, Asn1DerError> {
+        self.read_if(|tag| tag == T::TAG)
+    }
+    /// Reads the next subobject as `Defaulted<T, D>` if it is tagged with `T::TAG` or returns the
+    /// default value otherwise
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn read_defaulted<T, D>(&mut self) -> Result<Defaulted<T, D>, Asn1DerError>
+    where
+        T: DerDecodable<'a> + DerTagged + PartialEq,
+        D: DefaultValue<T>,
+    {
+        let value = self.read_optional().propagate(e!("Failed to read defaulted subobject"))?;
+        Ok(value.unwrap_or_default())
+    }
+    /// Reads the next subobject as `T` if its tag matches `matches_tag` or returns `None` otherwise
+    ///
+    /// _Note: This is useful for optional subobjects that can have one of several tags; e.g. an
+    /// `OPTIONAL` choice via `reader.read_if::<T, _>(T::matches_tag)`._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn read_if<T: DerDecodable<'a>, F: FnOnce(u8) -> bool>(
+        &mut self,
+        matches_tag: F,
+    ) -> Result<Option<T>, Asn1DerError> {
+        match self.peek().propagate(e!("Failed to read subobject"))? {
+            Some(object) if matches_tag(object.tag()) => {
+                Ok(Some(self.read().propagate(e!("Failed to read subobject"))?))
+            }
+            _ => Ok(None),
+        }
+    }
+    /// Ensures that all subobjects have been read
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    pub fn finish(self) -> Result<(), Asn1DerError> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(einval!("The sequence contains unexpected trailing subobjects")),
+        }
+    }
 }
 
 /// A newtype wrapper around `Vec` to work with sequences in a `Vec`-like way
@@ -151,40 +366,135 @@ impl<'a> DerEncodable for Sequence<'a> {
 /// _Note: We use a newtype wrapper here because Rust's generic type system does not allow
 /// specializations, so a direct implementation for `Vec<T>` would conflict with other
 /// implementations; e.g. the octet string implementation for `Vec<u8>`_
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 pub struct SequenceVec<T>(pub Vec<T>);
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<T> Deref for SequenceVec<T> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<T> DerefMut for SequenceVec<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<'a, T: DerDecodable<'a>> DerDecodable<'a> for SequenceVec<T> {
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
-        let sequence = Sequence::load(object).propagate(e!("Failed to load sequence"))?;
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let sequence = Sequence::load_with(object, options).propagate(e!("Failed to load sequence"))?;
+
+        // Draw the elements from the allocation budget before reserving them
+        let len = sequence.len();
+        let size = len.saturating_mul(mem::size_of::<T>());
+        let limits = sequence.options.limits.allocate(size).propagate(e!("Failed to allocate sequence"))?;
+        let mut options = DecodeOptions { limits, ..sequence.options };
 
         // Iterate through sub-objects once using sequential position tracking (O(n) instead of O(n²))
-        let mut vec = Vec::new();
+        let mut vec = Vec::with_capacity(len);
         let (mut pos, total_len) = (0, sequence.object.value().len());
         while pos < total_len {
             let subobject = sequence.subobject_at(&mut pos).propagate(e!("Failed to load subelement"))?;
-            let element: T = T::load(subobject).propagate(e!("Failed to load subelement"))?;
+            let element: T = T::load_with(subobject, options).propagate(e!("Failed to load subelement"))?;
+            options.limits =
+                options.limits.allocate(element.allocated_len()).propagate(e!("Failed to load subelement"))?;
             vec.push(element);
         }
         Ok(Self(vec))
     }
+    fn allocated_len(&self) -> usize {
+        let size = self.0.capacity().saturating_mul(mem::size_of::<T>());
+        self.0.iter().fold(size, |len, element| len.saturating_add(element.allocated_len()))
+    }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<T: DerEncodable> DerEncodable for SequenceVec<T> {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         Sequence::write(self, sink).propagate(e!("Failed to write sequence"))
     }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Sequence::write_len(self).propagate(e!("Failed to size sequence"))
+    }
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        Sequence::write_reverse(self, sink).propagate(e!("Failed to write sequence"))
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<T> DerTagged for SequenceVec<T> {
+    const TAG: u8 = Sequence::TAG;
+}
+
+/// A newtype wrapper around `heapless::Vec` to work with sequences of a fixed capacity
+///
+/// _Note: Like `SequenceVec`, this is a newtype wrapper because a direct implementation for
+/// `heapless::Vec<T, N>` would conflict with the octet string implementation for
+/// `heapless::Vec<u8, N>`_
+#[cfg(feature = "heapless")]
+pub struct HeaplessSequence<T, const N: usize>(pub heapless::Vec<T, N>);
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> core::ops::Deref for HeaplessSequence<T, N> {
+    type Target = [T];
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> core::ops::DerefMut for HeaplessSequence<T, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+#[cfg(feature = "heapless")]
+impl<'a, T: DerDecodable<'a>, const N: usize> DerDecodable<'a> for HeaplessSequence<T, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let sequence = Sequence::load_with(object, options).propagate(e!("Failed to load sequence"))?;
+
+        let mut vec = heapless::Vec::new();
+        let (mut pos, total_len, mut options) = (0, sequence.object.value().len(), sequence.options);
+        while pos < total_len {
+            let subobject = sequence.subobject_at(&mut pos).propagate(e!("Failed to load subelement"))?;
+            let element: T = T::load_with(subobject, options).propagate(e!("Failed to load subelement"))?;
+            options.limits =
+                options.limits.allocate(element.allocated_len()).propagate(e!("Failed to load subelement"))?;
+            if vec.push(element).is_err() {
+                Err(eunsupported!("The sequence exceeds the capacity of the vector"))?;
+            }
+        }
+        Ok(Self(vec))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.0.iter().fold(0, |len: usize, element| len.saturating_add(element.allocated_len()))
+    }
+}
+#[cfg(feature = "heapless")]
+impl<T: DerEncodable, const N: usize> DerEncodable for HeaplessSequence<T, N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        Sequence::write(self, sink).propagate(e!("Failed to write sequence"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        Sequence::write_len(self).propagate(e!("Failed to size sequence"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        Sequence::write_reverse(self, sink).propagate(e!("Failed to write sequence"))
+    }
+}
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> DerTagged for HeaplessSequence<T, N> {
+    const TAG: u8 = Sequence::TAG;
 }
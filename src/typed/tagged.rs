@@ -0,0 +1,154 @@
+use crate::error::ErrorChain;
+use crate::typed::{ConstructedWriter, DerDecodable, DerEncodable, DerTagged};
+use crate::{Asn1DerError, DecodeOptions, DerObject, ReverseSink, Sink};
+
+/// Computes the context-specific tag for the tag number `n`
+///
+/// _Note: Since this crate only supports single-byte tags, the tag number must not exceed `30`;
+/// larger tag numbers cause a compile-time error since the tag is always evaluated as constant._
+const fn context_tag(n: u8, constructed: bool) -> u8 {
+    match (n, constructed) {
+        (0..=30, true) => 0b1010_0000 | n,
+        (0..=30, false) => 0b1000_0000 | n,
+        _ => panic!("Context-specific tag numbers above 30 are not supported"),
+    }
+}
+
+/// A sink that replaces the first written element (i.e. the tag) with a context-specific tag
+struct RetaggingSink<'a, S: Sink> {
+    sink: &'a mut S,
+    /// The primitive context-specific tag
+    tag: Option<u8>,
+}
+impl<'a, S: Sink> Sink for RetaggingSink<'a, S> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn write(&mut self, e: u8) -> Result<(), Asn1DerError> {
+        match self.tag.take() {
+            // Keep the constructed-bit of the original tag
+            Some(tag) => self.sink.write(tag | (e & 0b0010_0000)),
+            None => self.sink.write(e),
+        }
+    }
+}
+
+/// An explicitly tagged value (`[N] EXPLICIT T`)
+///
+/// The value is wrapped into a constructed, context-specific object with the tag number `N`.
+///
+/// _Note: Since this crate only supports single-byte tags, `N` must not exceed `30`._
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Explicit<const N: u8, T>(pub T);
+impl<const N: u8, T> DerTagged for Explicit<N, T> {
+    const TAG: u8 = context_tag(N, true);
+}
+impl<'a, const N: u8, T: DerDecodable<'a>> DerDecodable<'a> for Explicit<N, T> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        if object.tag() != Self::TAG {
+            Err(einval!("DER object is not the expected explicitly tagged object"))?;
+        }
+
+        // Load the wrapped object which must span the entire value
+        let options = options.nested().propagate(e!("Failed to load explicitly tagged object"))?;
+        let inner = DerObject::decode_with(object.value(), options)
+            .propagate(e!("Failed to decode explicitly tagged object"))?;
+        if inner.raw().len() != object.value().len() {
+            Err(einval!("Explicitly tagged object contains trailing data"))?;
+        }
+        let value = T::load_with(inner, options).propagate(e!("Failed to load explicitly tagged object"))?;
+        Ok(Self(value))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.0.allocated_len()
+    }
+}
+impl<const N: u8, T: DerEncodable> DerEncodable for Explicit<N, T> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        ConstructedWriter::new(Self::TAG)
+            .push(&self.0)
+            .write(sink)
+            .propagate(e!("Failed to encode explicitly tagged object"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        let len = self.0.encoded_len().propagate(e!("Failed to size explicitly tagged object"))?;
+        DerObject::tlv_len(len)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        ConstructedWriter::new(Self::TAG)
+            .push(&self.0)
+            .write_reverse(sink)
+            .propagate(e!("Failed to encode explicitly tagged object"))
+    }
+}
+
+/// An implicitly tagged value (`[N] IMPLICIT T`)
+///
+/// The tag of the value is replaced by a context-specific tag with the tag number `N`; the
+/// constructed-bit of the original tag is retained.
+///
+/// _Note: Since this crate only supports single-byte tags, `N` must not exceed `30`._
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Implicit<const N: u8, T>(pub T);
+impl<const N: u8, T> Implicit<N, T> {
+    /// The primitive context-specific tag
+    ///
+    /// _Note: This is a constant so that `N > 30` is rejected at compile time instead of during encoding._
+    const PRIMITIVE_TAG: u8 = context_tag(N, false);
+}
+impl<const N: u8, T: DerTagged> DerTagged for Implicit<N, T> {
+    const TAG: u8 = context_tag(N, T::TAG & 0b0010_0000 != 0);
+}
+impl<'a, const N: u8, T: DerDecodable<'a> + DerTagged> DerDecodable<'a> for Implicit<N, T> {
+    /// Loads the object as `Self`
+    ///
+    /// _Note: The underlying object of a type view over the value still contains the context-specific tag in its `raw`
+    /// and `header` slices._
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        if object.tag() != Self::TAG {
+            Err(einval!("DER object is not the expected implicitly tagged object"))?;
+        }
+        let value =
+            T::load_with(object.with_tag(T::TAG), options).propagate(e!("Failed to load implicitly tagged object"))?;
+        Ok(Self(value))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn allocated_len(&self) -> usize {
+        self.0.allocated_len()
+    }
+}
+impl<const N: u8, T: DerEncodable> DerEncodable for Implicit<N, T> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        let mut sink = RetaggingSink { sink, tag: Some(Self::PRIMITIVE_TAG) };
+        self.0.encode(&mut sink).propagate(e!("Failed to encode implicitly tagged object"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        self.0.encoded_len()
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode_reverse<R: ReverseSink>(&self, sink: &mut R) -> Result<(), Asn1DerError> {
+        let written = sink.written_mut().len();
+        self.0.encode_reverse(sink).propagate(e!("Failed to encode implicitly tagged object"))?;
+
+        // Replace the tag, which is the first written byte, but keep its constructed-bit
+        let written_mut = sink.written_mut();
+        if let (true, Some(tag)) = (written_mut.len() > written, written_mut.first_mut()) {
+            *tag = Self::PRIMITIVE_TAG | (*tag & 0b0010_0000);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,13 @@
 use crate::error::ErrorChain;
-use crate::typed::{DerDecodable, DerEncodable, DerTypeView};
+use crate::typed::{DerDecodable, DerEncodable, DerTagged, DerTypeView};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use crate::DecodeOptions;
 use crate::{Asn1DerError, DerObject, Sink};
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
 use core::str;
 
 /// An ASN.1-DER UTF-8 string view
@@ -8,6 +15,7 @@ use core::str;
 pub struct Utf8String<'a> {
     object: DerObject<'a>,
 }
+owned_view!(Utf8StringBuf, Utf8String, "UTF-8 string");
 impl<'a> Utf8String<'a> {
     /// Writes a new UTF8String object with `value` into `sink` and returns a type view over it
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
@@ -21,24 +29,21 @@ impl<'a> Utf8String<'a> {
     // stdlib works correctly in this case
     pub fn get(&self) -> &str {
         let slice = self.object.value();
-        match str::from_utf8(slice) {
-            Ok(string) => string,
-            // #implicit_validation: Since we validate the string on `load`, this codepath is only
-            // possible if the underlying object has been modified in an invalid way
-            _ => "",
-        }
+        // #implicit_validation: Since we validate the string on `load`, the fallback is only
+        // possible if the underlying object has been modified in an invalid way
+        str::from_utf8(slice).unwrap_or_default()
     }
 
     /// Writes an UTF-8 string `value` as DER-object to `sink`
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     pub fn write<S: Sink>(value: &str, sink: &mut S) -> Result<(), Asn1DerError> {
-        DerObject::write(Self::TAG, value.len(), &mut value.as_bytes().iter(), sink)
-            .propagate(e!("Failed to write UTF-8 string"))
+        DerObject::write_slice(Self::TAG, value.as_bytes(), sink).propagate(e!("Failed to write UTF-8 string"))
     }
 }
-impl<'a> DerTypeView<'a> for Utf8String<'a> {
+impl<'a> DerTagged for Utf8String<'a> {
     const TAG: u8 = b'\x0c';
-
+}
+impl<'a> DerTypeView<'a> for Utf8String<'a> {
     #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
     fn object(&self) -> DerObject<'a> {
         self.object
@@ -48,13 +53,8 @@ impl<'a> DerDecodable<'a> for Utf8String<'a> {
     // _#implicit validation_: no_panic does not work due to `str::from_utf8`; however we just have to assume that the
     // stdlib works correctly in this case
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
-        match object.value() {
-            _ if object.tag() != Self::TAG => Err(einval!("DER object is not an UTF-8 string"))?,
-            s => match str::from_utf8(s).is_ok() {
-                true => Ok(Self { object }),
-                false => Err(einval!("DER object is not a valid UTF-8 string")),
-            },
-        }
+        <&str>::load(object).propagate(e!("Failed to load UTF-8 string"))?;
+        Ok(Self { object })
     }
 }
 impl<'a> DerEncodable for Utf8String<'a> {
@@ -63,18 +63,109 @@ impl<'a> DerEncodable for Utf8String<'a> {
     fn encode<U: Sink>(&self, sink: &mut U) -> Result<(), Asn1DerError> {
         self.object().encode(sink).propagate(e!("Failed to encode UTF-8 string"))
     }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.object.value().len())
+    }
+}
+
+impl<'a> DerDecodable<'a> for &'a str {
+    // _#implicit validation_: no_panic does not work due to `str::from_utf8`; however we just have to assume that the
+    // stdlib works correctly in this case
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        match object.value() {
+            _ if object.tag() != Utf8String::TAG => Err(einval!("DER object is not an UTF-8 string"))?,
+            s => match str::from_utf8(s) {
+                Ok(s) => Ok(s),
+                Err(_) => Err(einval!("DER object is not a valid UTF-8 string")),
+            },
+        }
+    }
+}
+impl DerEncodable for &str {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        Utf8String::write(self, sink).propagate(e!("Failed to encode UTF-8 string"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+impl DerTagged for &str {
+    const TAG: u8 = Utf8String::TAG;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl<'a> DerDecodable<'a> for Cow<'a, str> {
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        let string = <&str>::load(object).propagate(e!("Failed to load UTF-8 string"))?;
+        Ok(Cow::Borrowed(string))
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerEncodable for Cow<'_, str> {
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        Utf8String::write(self, sink).propagate(e!("Failed to encode UTF-8 string"))
+    }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerTagged for Cow<'_, str> {
+    const TAG: u8 = Utf8String::TAG;
 }
 
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl<'a> DerDecodable<'a> for String {
     fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
-        let string = Utf8String::load(object).propagate(e!("Failed to load UTF-8 string"))?;
+        Self::load_with(object, DecodeOptions::STRICT)
+    }
+    fn load_with(object: DerObject<'a>, options: DecodeOptions) -> Result<Self, Asn1DerError> {
+        let string = Utf8String::load_with(object, options).propagate(e!("Failed to load UTF-8 string"))?;
+        options.limits.check_alloc(string.get().len()).propagate(e!("Failed to allocate UTF-8 string"))?;
         Ok(string.get().to_string())
     }
+    fn allocated_len(&self) -> usize {
+        self.len()
+    }
 }
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 impl DerEncodable for String {
     fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
         Utf8String::write(self, sink).propagate(e!("Failed to encode UTF-8 string"))
     }
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+impl DerTagged for String {
+    const TAG: u8 = Utf8String::TAG;
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> DerDecodable<'a> for heapless::String<N> {
+    fn load(object: DerObject<'a>) -> Result<Self, Asn1DerError> {
+        let string = <&str>::load(object).propagate(e!("Failed to load UTF-8 string"))?;
+        let mut this = Self::new();
+        this.push_str(string).map_err(|_| eunsupported!("The UTF-8 string exceeds the capacity of the string"))?;
+        Ok(this)
+    }
+}
+#[cfg(feature = "heapless")]
+impl<const N: usize> DerEncodable for heapless::String<N> {
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encode<S: Sink>(&self, sink: &mut S) -> Result<(), Asn1DerError> {
+        Utf8String::write(self, sink).propagate(e!("Failed to encode UTF-8 string"))
+    }
+    #[cfg_attr(feature = "no_panic", no_panic::no_panic)]
+    fn encoded_len(&self) -> Result<usize, Asn1DerError> {
+        DerObject::tlv_len(self.len())
+    }
+}
+#[cfg(feature = "heapless")]
+impl<const N: usize> DerTagged for heapless::String<N> {
+    const TAG: u8 = Utf8String::TAG;
 }
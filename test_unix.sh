@@ -6,8 +6,27 @@ cargo test --verbose --release --no-default-features
 cargo test --verbose --release --no-default-features --features="std"
 cargo test --verbose --release --no-default-features --features="native_types"
 cargo test --verbose --release --no-default-features --features="std,native_types"
+cargo test --verbose --release --workspace --features="derive"
+cargo test --verbose --release --no-default-features --features="embedded_io"
+cargo test --verbose --release --no-default-features --features="embedded_io_async"
+cargo test --verbose --release --no-default-features --features="tokio_io"
+cargo test --verbose --release --no-default-features --features="std,memmap2"
+cargo test --verbose --release --no-default-features --features="bytes"
+cargo test --verbose --release --no-default-features --features="std,bytes"
+cargo test --verbose --release --no-default-features --features="heapless"
+cargo test --verbose --release --no-default-features --features="heapless,native_types"
+cargo test --verbose --release --no-default-features --features="alloc"
+cargo test --verbose --release --no-default-features --features="alloc,native_types"
 
 cargo run --verbose --release --no-default-features --features="no_panic" --example="nopanic"
 cargo run --verbose --release --no-default-features --features="no_panic,std" --example="nopanic"
 cargo run --verbose --release --no-default-features --features="no_panic,native_types" --example="nopanic"
 cargo run --verbose --release --no-default-features --features="no_panic,std,native_types" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,embedded_io" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,embedded_io_async" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,tokio_io" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,std,memmap2" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,bytes" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,heapless,native_types" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,alloc" --example="nopanic"
+cargo run --verbose --release --no-default-features --features="no_panic,alloc,native_types" --example="nopanic"
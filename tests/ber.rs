@@ -0,0 +1,160 @@
+#![cfg_attr(test, deny(warnings))]
+
+pub mod helpers;
+
+use crate::helpers::{OptionExt, ResultExt};
+use asn1_der::ber::length::{self, Length};
+use asn1_der::{BerObject, DerObject};
+
+#[test]
+fn length() {
+    // Minimal, non-minimal and indefinite lengths
+    for (bytes, len) in [
+        (b"\x05".as_slice(), Length::Definite(5)),
+        (b"\x81\x05", Length::Definite(5)),
+        (b"\x84\x00\x00\x01\x00", Length::Definite(256)),
+        (b"\x80", Length::Indefinite),
+    ] {
+        let decoded = length::decode(&mut bytes.iter()).assert("length").expect("Truncated length");
+        assert_eq!(decoded, len);
+    }
+
+    // Truncated and reserved lengths
+    assert!(length::decode(&mut b"\x82\x01".iter()).assert("truncated").is_none());
+    length::decode(&mut b"\xff".iter()).assert_err("InvalidData", "reserved");
+}
+
+#[test]
+fn object() {
+    // A non-minimal length
+    let object = BerObject::decode(b"\x04\x84\x00\x00\x00\x02\xaa\xbb\x05\x00").assert("non-minimal");
+    assert_eq!(
+        (object.tag(), object.header(), object.value()),
+        (0x04, b"\x04\x84\x00\x00\x00\x02".as_slice(), b"\xaa\xbb".as_slice())
+    );
+    assert_eq!(object.raw().len(), 8);
+
+    // Nested indefinite lengths
+    const NESTED: &[u8] = b"\x30\x80\x02\x01\x05\x30\x80\x01\x01\xff\x00\x00\x00\x00";
+    let object = BerObject::decode(NESTED).assert("indefinite");
+    assert!(object.is_constructed() && object.is_indefinite());
+    assert_eq!(object.raw(), NESTED);
+    assert_eq!(object.value(), &NESTED[2..12]);
+
+    let mut children = object.children();
+    assert_eq!(children.next().expect("Missing child").assert("child").raw(), b"\x02\x01\x05");
+    let nested = children.next().expect("Missing child").assert("child");
+    assert!(nested.is_indefinite());
+    assert_eq!(nested.value(), b"\x01\x01\xff");
+    assert!(children.next().is_none());
+
+    // DER objects are BER objects
+    let object = BerObject::from(DerObject::decode(b"\x02\x01\x07").assert("der"));
+    assert_eq!((object.tag(), object.value(), object.is_indefinite()), (0x02, b"\x07".as_slice(), false));
+}
+
+#[test]
+fn segments() {
+    // A constructed OCTET STRING with a nested constructed segment
+    const OCTET_STRING: &[u8] = b"\x24\x80\x04\x02\x01\x02\x24\x04\x04\x02\x03\x04\x00\x00";
+    let object = BerObject::decode(OCTET_STRING).assert("octet string");
+    let segments: Vec<_> = object.segments().assert("octet string").map(|s| s.assert("segment")).collect();
+    assert_eq!(segments, [b"\x01\x02".as_slice(), b"\x03\x04".as_slice()]);
+
+    let mut value = [0; 4];
+    object.copy_value(&mut value.iter_mut()).assert("octet string");
+    assert_eq!(&value, b"\x01\x02\x03\x04");
+
+    // A primitive string is a single segment
+    let object = BerObject::decode(b"\x0c\x02hi").assert("utf8 string");
+    let segments: Vec<_> = object.segments().assert("utf8 string").map(|s| s.assert("segment")).collect();
+    assert_eq!(segments, [b"hi".as_slice()]);
+
+    // A constructed BIT STRING is reassembled with the unused bits of the last segment
+    let object = BerObject::decode(b"\x23\x09\x03\x02\x00\xaa\x03\x03\x04\xbb\xc0").assert("bit string");
+    let mut value = [0; 4];
+    object.copy_value(&mut value.iter_mut()).assert("bit string");
+    assert_eq!(&value, b"\x04\xaa\xbb\xc0");
+}
+
+#[test]
+fn err() {
+    // Primitive indefinite length, missing end-of-contents and end-of-contents with a length
+    BerObject::decode(b"\x04\x80\x00\x00").assert_err("InvalidData", "primitive indefinite");
+    BerObject::decode(b"\x30\x80\x02\x01\x05").assert_err("InOutError", "missing end-of-contents");
+    BerObject::decode(b"\x30\x80\x00\x01\x00\x00\x00").assert_err("InvalidData", "end-of-contents length");
+    BerObject::decode(b"\x04\x03\x01\x02").assert_err("InOutError", "truncated");
+
+    // Segments of a non-string, invalid segment tags and unused bits in a non-last BIT STRING segment
+    let object = BerObject::decode(b"\x30\x03\x02\x01\x05").assert("sequence");
+    object.segments().map(|_| ()).assert_err("InvalidData", "sequence segments");
+    let object = BerObject::decode(b"\x24\x03\x02\x01\x05").assert("octet string");
+    object.copy_value(&mut [0; 1].iter_mut()).assert_err("InvalidData", "segment tag");
+    let object = BerObject::decode(b"\x23\x08\x03\x02\x04\xaa\x03\x02\x00\xbb").assert("bit string");
+    object.copy_value(&mut [0; 3].iter_mut()).assert_err("InvalidData", "unused bits");
+
+    // End-of-contents markers are only allowed within indefinite length values
+    let object = BerObject::decode(b"\x24\x04\x00\x00\x04\x00").assert("definite string");
+    object.segments().map(|_| ()).assert_err("InvalidData", "definite end-of-contents");
+    let object = BerObject::decode(b"\x24\x80\x24\x04\x00\x00\x04\x00\x00\x00").assert("indefinite string");
+    let mut segments = object.segments().assert("indefinite string");
+    segments.next().assert("nested end-of-contents").assert_err("InvalidData", "nested end-of-contents");
+    let object = BerObject::decode(b"\x24\x07\x24\x80\x04\x01\xaa\x00\x00").assert("nested indefinite string");
+    let segments: Vec<_> = object.segments().assert("nested indefinite string").map(|s| s.assert("segment")).collect();
+    assert_eq!(segments, [b"\xaa".as_slice()]);
+}
+
+#[test]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+fn canonicalize() {
+    use asn1_der::ber::{self, Change, Transformation::*};
+
+    // A SET with an indefinite length, a constructed string, a non-minimal length and INTEGER, a BER
+    // BOOLEAN and a BIT STRING with non-zero padding
+    const BER: &[u8] =
+        b"\x31\x80\x24\x80\x04\x01\xaa\x04\x01\xbb\x00\x00\x02\x82\x00\x02\x00\x05\x01\x01\x01\x03\x02\x04\xff\x00\x00";
+    const DER: &[u8] = b"\x31\x0e\x01\x01\xff\x02\x01\x05\x03\x02\x04\xf0\x04\x02\xaa\xbb";
+    let mut der = Vec::new();
+    let changes = ber::canonicalize(BER, &mut der).assert("canonicalize");
+    assert_eq!(der, DER);
+
+    let change = |offset, tag, transformation| Change { offset, tag, transformation };
+    assert_eq!(
+        changes,
+        [
+            change(0, 0x31, IndefiniteLength),
+            change(2, 0x24, IndefiniteLength),
+            change(2, 0x24, ConstructedString),
+            change(12, 0x02, NonMinimalLength),
+            change(12, 0x02, NonMinimalInteger),
+            change(18, 0x01, NonDerBoolean),
+            change(21, 0x03, BitStringPadding),
+            change(0, 0x31, UnsortedSet),
+        ]
+    );
+
+    // DER is left untouched
+    let mut der = Vec::new();
+    assert!(ber::canonicalize(DER, &mut der).assert("canonicalize").is_empty());
+    assert_eq!(der, DER);
+
+    // The components of a SET are sorted by tag number regardless of the constructed-bit, whereas
+    // the elements of a SET OF are sorted by their encoding
+    const SET: &[u8] = b"\x31\x08\xa0\x03\x02\x01\x05\x81\x01\x07";
+    let mut der = Vec::new();
+    assert!(ber::canonicalize(SET, &mut der).assert("canonicalize").is_empty());
+    assert_eq!(der, SET);
+    let mut der = Vec::new();
+    let changes = ber::canonicalize(b"\x31\x08\x81\x01\x07\xa0\x03\x02\x01\x05", &mut der).assert("canonicalize");
+    assert_eq!((der.as_slice(), changes.as_slice()), (SET, [change(0, 0x31, UnsortedSet)].as_slice()));
+    let mut der = Vec::new();
+    let changes = ber::canonicalize(b"\x31\x06\x02\x01\x07\x02\x01\x05", &mut der).assert("canonicalize");
+    assert_eq!(der, b"\x31\x06\x02\x01\x05\x02\x01\x07");
+    assert_eq!(changes, [change(0, 0x31, UnsortedSet)]);
+
+    // Trailing data and invalid values
+    ber::canonicalize(b"\x05\x00\x05\x00", &mut Vec::new()).assert_err("InvalidData", "trailing data");
+    ber::canonicalize(b"\x30\x04\x01\x02\x00\x00", &mut Vec::new()).assert_err("InvalidData", "boolean length");
+    ber::canonicalize(b"\x30\x02\x02\x00", &mut Vec::new()).assert_err("InvalidData", "empty integer");
+    ber::canonicalize(b"\x03\x01\x01", &mut Vec::new()).assert_err("InvalidData", "bit string");
+}
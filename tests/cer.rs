@@ -0,0 +1,144 @@
+#![cfg_attr(test, deny(warnings))]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::cer::{self, CerSink};
+use asn1_der::{DerObject, Sink};
+
+/// Converts the DER object `der` into CER
+fn cer(der: &[u8]) -> Vec<u8> {
+    let (mut buf, mut len) = (vec![0; der.len() * 2], 0);
+    let mut sink = CerSink::new(buf.iter_mut().counting_sink(&mut len));
+    DerObject::decode(der).assert("der").encode(&mut sink).assert("cer");
+    sink.finish().map(|_| ()).assert("cer");
+
+    buf.truncate(len);
+    buf
+}
+
+#[test]
+fn constructed() {
+    // Constructed objects use the indefinite length form
+    const DER: &[u8] = b"\x30\x0a\x02\x01\x05\x30\x03\x01\x01\xff\x30\x00";
+    const CER: &[u8] = b"\x30\x80\x02\x01\x05\x30\x80\x01\x01\xff\x00\x00\x30\x80\x00\x00\x00\x00";
+    assert_eq!(cer(DER), CER);
+    cer::validate(CER).assert("validate");
+
+    // Primitive objects are left untouched
+    assert_eq!(cer(b"\x04\x03abc"), b"\x04\x03abc");
+    cer::validate(b"\x04\x03abc").assert("validate");
+}
+
+#[test]
+fn set() {
+    // The DER order of primitive elements and of a single element is retained in CER
+    const DER: &[u8] = b"\x31\x06\x02\x01\x05\x02\x01\x06";
+    let cer_set = cer(DER);
+    assert_eq!(cer_set, b"\x31\x80\x02\x01\x05\x02\x01\x06\x00\x00");
+    cer::validate(&cer_set).assert("validate");
+    let cer_set = cer(b"\x31\x05\x30\x03\x02\x01\x02");
+    assert_eq!(cer_set, b"\x31\x80\x30\x80\x02\x01\x02\x00\x00\x00\x00");
+    cer::validate(&cer_set).assert("validate");
+
+    // The CER encodings of `SEQUENCE { 1, 1 }` and `SEQUENCE { 2 }` are sorted differently than
+    // their DER encodings
+    const UNSORTED: &[u8] = b"\x31\x0d\x30\x03\x02\x01\x02\x30\x06\x02\x01\x01\x02\x01\x01";
+    let mut buf = [0; 32];
+    let mut sink = CerSink::new(buf.iter_mut());
+    DerObject::decode(UNSORTED).assert("der").encode(&mut sink).assert_err("Unsupported", "set");
+}
+
+#[test]
+fn segments() {
+    // Strings with more than 1000 octets are segmented
+    let mut der = b"\x04\x82\x09\xc4".to_vec();
+    der.extend((0..2500).map(|i| i as u8));
+    let mut expected = b"\x24\x80\x04\x82\x03\xe8".to_vec();
+    expected.extend_from_slice(&der[4..1004]);
+    expected.extend_from_slice(b"\x04\x82\x03\xe8");
+    expected.extend_from_slice(&der[1004..2004]);
+    expected.extend_from_slice(b"\x04\x82\x01\xf4");
+    expected.extend_from_slice(&der[2004..]);
+    expected.extend_from_slice(b"\x00\x00");
+    assert_eq!(cer(&der), expected);
+    cer::validate(&expected).assert("validate");
+
+    // Only the last segment of a BIT STRING has unused bits
+    let mut der = b"\x03\x82\x05\xdd\x03".to_vec();
+    der.extend((0..1500).map(|_| 0xf8));
+    let mut expected = b"\x23\x80\x03\x82\x03\xe8\x00".to_vec();
+    expected.extend_from_slice(&der[5..1004]);
+    expected.extend_from_slice(b"\x03\x82\x01\xf6\x03");
+    expected.extend_from_slice(&der[1004..]);
+    expected.extend_from_slice(b"\x00\x00");
+    assert_eq!(cer(&der), expected);
+    cer::validate(&expected).assert("validate");
+
+    // Exactly 1000 octets are not segmented
+    let mut der = b"\x04\x82\x03\xe8".to_vec();
+    der.extend((0..1000).map(|i| i as u8));
+    assert_eq!(cer(&der), der);
+
+    // Implicitly tagged strings are not recognized as strings
+    let mut der = b"\x84\x82\x04\xb0".to_vec();
+    der.extend((0..1200).map(|i| i as u8));
+    assert_eq!(cer(&der), der);
+    cer::validate(&der).assert("validate");
+
+    // A SET with more than one element must not contain long strings
+    let mut der = b"\x31\x82\x04\xb7\x02\x01\x05\x04\x82\x04\xb0".to_vec();
+    der.extend((0..1200).map(|i| i as u8));
+    let mut buf = vec![0; der.len() * 2];
+    let mut sink = CerSink::new(buf.iter_mut());
+    DerObject::decode(&der).assert("der").encode(&mut sink).assert_err("Unsupported", "set");
+}
+
+#[test]
+#[cfg(feature = "native_types")]
+fn encode_cer() {
+    use asn1_der::typed::{ConstructedWriter, DerEncodable};
+
+    let writer = ConstructedWriter::sequence().push(5u8).push(true);
+    let mut buf = [0; 10];
+    writer.encode_cer(&mut buf.iter_mut()).assert("encode_cer");
+    assert_eq!(&buf, b"\x30\x80\x02\x01\x05\x01\x01\xff\x00\x00");
+}
+
+#[test]
+fn err() {
+    // Incomplete DER objects
+    let mut buf = [0; 8];
+    let mut sink = CerSink::new(buf.iter_mut());
+    b"\x30\x03\x02\x01".iter().try_for_each(|b| sink.write(*b)).assert("write");
+    sink.finish().map(|_| ()).assert_err("InOutError", "truncated");
+    let mut sink = CerSink::new(buf.iter_mut());
+    b"\x30\x02\x02\x01".iter().try_for_each(|b| sink.write(*b)).assert_err("InvalidData", "exceeds parent");
+
+    // Definite constructed lengths, non-minimal lengths and non-DER values
+    cer::validate(b"\x30\x03\x02\x01\x05").assert_err("InvalidData", "definite length");
+    cer::validate(b"\x04\x81\x01\x00").assert_err("InvalidData", "non-minimal length");
+    cer::validate(b"\x30\x80\x01\x01\x01\x00\x00").assert_err("InvalidData", "boolean");
+    cer::validate(b"\x02\x02\x00\x05").assert_err("InvalidData", "integer");
+    cer::validate(b"\x03\x02\x04\xff").assert_err("InvalidData", "bit string padding");
+    cer::validate(b"\x31\x80\x02\x01\x06\x02\x01\x05\x00\x00").assert_err("InvalidData", "unsorted set");
+
+    // Short constructed strings and short non-last segments
+    cer::validate(b"\x24\x80\x04\x01\x01\x00\x00").assert_err("InvalidData", "short string");
+    let mut segments = b"\x24\x80\x04\x01\x01\x04\x82\x03\xe8".to_vec();
+    segments.extend((0..1000).map(|_| 0));
+    segments.extend_from_slice(b"\x00\x00");
+    cer::validate(&segments).assert_err("InvalidData", "short segment");
+
+    // A BIT STRING with 999 data bytes fits into a primitive encoding, even though its segments sum up to 1001
+    //  contents octets, and a trailing segment without data bits is never necessary
+    let mut bit_string = b"\x23\x80\x03\x82\x03\xe8\x00".to_vec();
+    bit_string.extend((0..999).map(|_| 0));
+    let mut trailing = bit_string.clone();
+    bit_string.extend_from_slice(b"\x03\x01\x00\x00\x00");
+    cer::validate(&bit_string).assert_err("InvalidData", "short bit string");
+    trailing.extend_from_slice(b"\x03\x82\x03\xe8\x00");
+    trailing.extend((0..999).map(|_| 0));
+    trailing.extend_from_slice(b"\x03\x01\x00\x00\x00");
+    cer::validate(&trailing).assert_err("InvalidData", "empty last bit string segment");
+}
@@ -0,0 +1,145 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(feature = "embedded_io")]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{DerObject, EmbeddedSink, EmbeddedSource, Sink, SliceSink, Source};
+use embedded_io::{ErrorKind, ErrorType, Read, SliceWriteError, Write};
+
+/// A reader that returns at most one byte per call and fails with `ErrorKind::Other` at the end
+struct Trickle<'a>(&'a [u8]);
+impl ErrorType for Trickle<'_> {
+    type Error = ErrorKind;
+}
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        match (self.0, buf) {
+            ([], _) => Err(ErrorKind::Other),
+            ([next, rest @ ..], [target, ..]) => {
+                *target = *next;
+                self.0 = rest;
+                Ok(1)
+            }
+            (_, []) => Ok(0),
+        }
+    }
+}
+
+/// A misbehaving writer that accepts nothing but does not report an error
+struct Stuck;
+impl ErrorType for Stuck {
+    type Error = ErrorKind;
+}
+impl Write for Stuck {
+    fn write(&mut self, _buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn source() {
+    let raw = b"\x04\x03\x01\x02\x03\x05\x00";
+    let (mut buf, mut pos) = ([0; 8], 0);
+    let mut source = EmbeddedSource::new(Trickle(raw));
+    let object = DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut pos)).assert("embedded");
+    assert_eq!((object.tag(), object.value()), (0x04, b"\x01\x02\x03".as_slice()));
+    assert!(source.take_error().is_none());
+
+    // The reader error is kept
+    let mut buf = [0; 3];
+    source.read_exact(&mut buf).assert_err("InOutError", "embedded");
+    assert_eq!(source.take_error(), Some(ErrorKind::Other));
+    assert_eq!(source.into_inner().0, b"");
+
+    // The end of a reader is an error
+    EmbeddedSource::new(b"\x04".as_slice()).read_exact(&mut buf).assert_err("InOutError", "embedded");
+}
+
+#[test]
+fn sink() {
+    let mut buf = [0; 4];
+    let mut sink = EmbeddedSink::new(buf.as_mut_slice());
+    DerObject::decode(b"\x05\x00").assert("embedded").encode(&mut sink).assert("embedded");
+    sink.write(0x07).assert("embedded");
+    assert_eq!(sink.into_inner().len(), 1);
+    assert_eq!(buf, [0x05, 0x00, 0x07, 0x00]);
+
+    // The writer error is kept
+    let mut buf = [0; 2];
+    let mut sink = EmbeddedSink::new(buf.as_mut_slice());
+    sink.write_all(b"\x02\x01\x07").assert_err("InOutError", "embedded");
+    assert_eq!(sink.take_error(), Some(SliceWriteError::Full));
+
+    // A writer that accepts nothing is an error
+    let mut sink = EmbeddedSink::new(Stuck);
+    sink.write_all(b"\x05\x00").assert_err("InOutError", "embedded");
+    assert!(sink.take_error().is_none());
+}
+
+#[cfg(feature = "embedded_io_async")]
+mod asynchronous {
+    use crate::helpers::ResultExt;
+    use asn1_der::{AsyncEmbeddedSink, AsyncEmbeddedSource, DerObject, SliceSink};
+    use embedded_io::SliceWriteError;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Polls `future` to completion; the adapters never return `Poll::Pending` for slices
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn source() {
+        let mut long = vec![0x04, 0x82, 0x01, 0x2c];
+        long.extend((0..300).map(|i| i as u8));
+        let mut raw = long.clone();
+        raw.extend_from_slice(b"\x05\x00\x04\x81\x01\x04\x89");
+
+        let mut source = AsyncEmbeddedSource::new(raw.as_slice());
+        let (mut buf, mut pos) = ([0; 512], 0);
+        let object = block_on(source.decode(SliceSink::new(&mut buf, &mut pos))).assert("embedded");
+        assert_eq!(object.raw(), long.as_slice());
+
+        let (mut buf, mut pos) = ([0; 512], 0);
+        let object = block_on(source.decode(SliceSink::new(&mut buf, &mut pos))).assert("embedded");
+        assert_eq!(object.raw(), b"\x05\x00");
+
+        // Invalid and unsupported lengths
+        let (mut buf, mut pos) = ([0; 512], 0);
+        block_on(source.decode(SliceSink::new(&mut buf, &mut pos))).assert_err("InvalidData", "embedded");
+        let (mut buf, mut pos) = ([0; 512], 0);
+        block_on(source.decode(SliceSink::new(&mut buf, &mut pos))).assert_err("Unsupported", "embedded");
+
+        // Truncated objects
+        let (mut buf, mut pos) = ([0; 512], 0);
+        let mut source = AsyncEmbeddedSource::new(b"\x04\x03\x01".as_slice());
+        block_on(source.decode(SliceSink::new(&mut buf, &mut pos))).assert_err("InOutError", "embedded");
+    }
+
+    #[test]
+    fn sink() {
+        let mut buf = [0; 5];
+        let mut sink = AsyncEmbeddedSink::new(buf.as_mut_slice());
+        block_on(sink.write_object(DerObject::decode(b"\x05\x00").assert("embedded"))).assert("embedded");
+        #[cfg(feature = "native_types")]
+        block_on(sink.encode(&7u8, &mut [0; 3])).assert("embedded");
+        #[cfg(feature = "native_types")]
+        assert_eq!(buf, [0x05, 0x00, 0x02, 0x01, 0x07]);
+
+        let mut buf = [0; 1];
+        let mut sink = AsyncEmbeddedSink::new(buf.as_mut_slice());
+        block_on(sink.write_all(b"\x05\x00")).assert_err("InOutError", "embedded");
+        assert_eq!(sink.take_error(), Some(SliceWriteError::Full));
+    }
+}
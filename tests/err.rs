@@ -3,7 +3,7 @@
 pub mod helpers;
 
 use crate::helpers::{test_err, ResultExt};
-use asn1_der::{der, DerObject};
+use asn1_der::{der, DecodeLimits, DecodeOptions, DerObject, Header, Sink, SliceSink, Source};
 
 #[test]
 fn length() {
@@ -18,3 +18,78 @@ fn object() {
         DerObject::decode(test.bytes.as_slice()).assert_err(test.err(), &test.name);
     }
 }
+
+#[test]
+fn header() {
+    for test in test_err::load().length {
+        let bytes: Vec<u8> = [0x04].iter().chain(test.bytes.iter()).copied().collect();
+        Header::decode(&bytes).assert_err(&test.err, &test.name);
+    }
+
+    // Truncated headers
+    Header::decode(b"").assert_err("InOutError", "empty header");
+    Header::decode(b"\x04").assert_err("InOutError", "missing length");
+    Header::decode(b"\x04\x82\x01").assert_err("InOutError", "truncated length");
+
+    // Non-minimal lengths can be allowed explicitly
+    Header::decode(b"\x04\x81\x01").assert_err("InvalidData", "non-minimal length");
+    Header::decode_with(b"\x04\x81\x01", DecodeOptions::LENIENT).assert("non-minimal length");
+
+    // The indefinite length form is not a non-minimal length
+    const INDEFINITE: &[u8] = b"\x30\x80\x02\x01\x05\x00\x00";
+    Header::decode_with(INDEFINITE, DecodeOptions::LENIENT).assert_err("InvalidData", "indefinite length");
+    DerObject::decode_with(INDEFINITE, DecodeOptions::LENIENT)
+        .map(|_| ())
+        .assert_err("InvalidData", "indefinite length");
+}
+
+#[test]
+fn bulk() {
+    // Reads and writes beyond the end fail
+    let mut buf = [0; 4];
+    b"\x01\x02\x03".iter().read_exact(&mut buf).assert_err("InOutError", "read beyond end");
+
+    let mut buf = [0; 3];
+    let mut sink = buf.iter_mut();
+    sink.write_all(b"\x01\x02\x03\x04").assert_err("InOutError", "write beyond end");
+    sink.write_all(b"\x01\x02\x03").assert("write after error");
+    assert_eq!(buf, [1, 2, 3]);
+
+    let (mut buf, mut pos) = ([0; 3], 1);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    sink.write_all(b"\x01\x02\x03").assert_err("InOutError", "write beyond end");
+    sink.write_all(b"\x01\x02").assert("write after error");
+    assert_eq!((buf, pos), ([0, 1, 2], 3));
+
+    // A truncated value fails while being copied
+    let (mut buf, mut pos) = ([0; 1024], 0);
+    let mut source = b"\x04\x82\x01\x00\x01\x02".iter();
+    DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut pos))
+        .assert_err("InOutError", "truncated value");
+}
+
+#[test]
+fn limits() {
+    let limits = DecodeLimits { max_object_len: 4, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+
+    // The header is counted into the object length
+    DerObject::decode_with(b"\x04\x02\x01\x02", options).assert("object within limit");
+    DerObject::decode_with(b"\x04\x03\x01\x02\x03", options).assert_err("LimitExceeded", "object too long");
+    Header::decode_with(b"\x04\x81\x80", options).assert_err("LimitExceeded", "header too long");
+
+    // Hostile lengths are rejected before any byte is copied
+    let (mut buf, mut pos) = ([0; 16], 0);
+    let mut source = b"\x04\x88\x7f\xff\xff\xff\xff\xff\xff\xff".iter();
+    DerObject::decode_from_source_with(&mut source, SliceSink::new(&mut buf, &mut pos), options)
+        .assert_err("LimitExceeded", "hostile length");
+    assert_eq!(pos, 10);
+
+    // The copied value is counted as allocation
+    let limits = DecodeLimits { max_alloc: 2, ..DecodeLimits::UNLIMITED };
+    let options = DecodeOptions { limits, ..DecodeOptions::STRICT };
+    let (mut buf, mut pos) = ([0; 16], 0);
+    let mut source = b"\x04\x03\x01\x02\x03".iter();
+    DerObject::decode_from_source_with(&mut source, SliceSink::new(&mut buf, &mut pos), options)
+        .assert_err("LimitExceeded", "allocation too large");
+}
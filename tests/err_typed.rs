@@ -4,7 +4,11 @@
 pub mod helpers;
 
 use crate::helpers::{test_err, ResultExt};
-use asn1_der::typed::{Boolean, DerDecodable, Integer, Null, OctetString, Sequence, Utf8String};
+use asn1_der::typed::{
+    Boolean, ConstructedWriter, Defaulted, DerDecodable, DerEncodable, Explicit, Integer, Null, OctetString, Sequence,
+    TypeDefault, Utf8String,
+};
+use asn1_der::{DecodeLimits, DecodeOptions, DerObject, ReverseSliceSink};
 
 #[test]
 fn boolean() {
@@ -39,8 +43,11 @@ fn null() {
 fn octet_string() {
     for test in test_err::load().typed.octet_string {
         OctetString::decode(&test.bytes).assert_err(&test.err, &test.name);
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        <&[u8]>::decode(&test.bytes).assert_err(&test.err, &test.name);
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         Vec::<u8>::decode(&test.bytes).assert_err(&test.err, &test.name);
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        std::borrow::Cow::<[u8]>::decode(&test.bytes).assert_err(&test.err, &test.name);
     }
 }
 
@@ -51,11 +58,128 @@ fn sequence() {
     }
 }
 
+#[test]
+fn defaulted() {
+    // Explicitly encoded default values
+    Defaulted::<bool>::decode(b"\x01\x01\x00").assert_err("InvalidData", "defaulted");
+    let sequence = Sequence::decode(b"\x30\x03\x02\x01\x00").assert("defaulted");
+    sequence.reader().read_defaulted::<u8, TypeDefault>().assert_err("InvalidData", "defaulted");
+}
+
 #[test]
 fn utf8_string() {
     for test in test_err::load().typed.utf8_string {
         Utf8String::decode(&test.bytes).assert_err(&test.err, &test.name);
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        <&str>::decode(&test.bytes).assert_err(&test.err, &test.name);
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         String::decode(&test.bytes).assert_err(&test.err, &test.name);
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+        std::borrow::Cow::<str>::decode(&test.bytes).assert_err(&test.err, &test.name);
+    }
+}
+
+#[test]
+fn decode_options() {
+    // Strict DER rejects each laxness
+    bool::decode(b"\x01\x01\x01").assert_err("InvalidData", "BER boolean");
+    u16::decode(b"\x02\x03\x00\x00\xff").assert_err("InvalidData", "non-minimal integer");
+    DerObject::decode(b"\x04\x82\x00\x01\x07").assert_err("InvalidData", "non-minimal length");
+
+    // A flag does not enable the other laxnesses
+    let options = DecodeOptions { allow_non_minimal_lengths: true, ..DecodeOptions::STRICT };
+    bool::decode_with(b"\x01\x81\x01\x01", options).assert_err("InvalidData", "BER boolean");
+    let options = DecodeOptions { allow_ber_booleans: true, ..DecodeOptions::STRICT };
+    bool::decode_with(b"\x01\x81\x01\xff", options).assert_err("InvalidData", "non-minimal length");
+    Sequence::decode_with(b"\x30\x04\x02\x82\x00\x01", options).assert_err("InvalidData", "non-minimal length");
+
+    // Lenient decoding still rejects invalid objects
+    let options = DecodeOptions::LENIENT;
+    bool::decode_with(b"\x01\x02\xff\xff", options).assert_err("InvalidData", "invalid boolean");
+    u8::decode_with(b"\x02\x00", options).assert_err("InvalidData", "empty integer");
+    u8::decode_with(b"\x02\x03\x00\x01\x00", options).assert_err("Unsupported", "numeric value too large");
+}
+
+#[test]
+fn decode_limits() {
+    /// Creates strict options with `limits`
+    fn options(limits: DecodeLimits) -> DecodeOptions {
+        DecodeOptions { limits, ..DecodeOptions::STRICT }
+    }
+
+    // Each constructed level consumes one level of depth
+    let nested = b"\x30\x07\xa0\x05\x30\x03\x02\x01\x07";
+    let depth = |max_depth| options(DecodeLimits { max_depth, ..DecodeLimits::UNLIMITED });
+    Sequence::decode_with(nested, depth(1)).assert("depth within limit");
+    Explicit::<0, u8>::decode_with(&nested[2..], depth(0)).assert_err("LimitExceeded", "explicit too deep");
+    Sequence::decode_with(nested, depth(0)).assert_err("LimitExceeded", "sequence too deep");
+
+    // The elements are counted while the sequence is validated
+    let elements = options(DecodeLimits { max_elements: 2, ..DecodeLimits::UNLIMITED });
+    Sequence::decode_with(b"\x30\x04\x05\x00\x05\x00", elements).assert("elements within limit");
+    Sequence::decode_with(b"\x30\x06\x05\x00\x05\x00\x05\x00", elements)
+        .assert_err("LimitExceeded", "too many elements");
+
+    // Allocating types check their buffer size
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+    {
+        use asn1_der::typed::SequenceVec;
+        use std::mem;
+
+        let alloc = options(DecodeLimits { max_alloc: 2, ..DecodeLimits::UNLIMITED });
+        Vec::<u8>::decode_with(b"\x04\x02\x01\x02", alloc).assert("allocation within limit");
+        Vec::<u8>::decode_with(b"\x04\x03\x01\x02\x03", alloc).assert_err("LimitExceeded", "octet string too large");
+        String::decode_with(b"\x0c\x03abc", alloc).assert_err("LimitExceeded", "UTF-8 string too large");
+        SequenceVec::<u16>::decode_with(b"\x30\x06\x02\x01\x01\x02\x01\x02", alloc)
+            .map(|_| ())
+            .assert_err("LimitExceeded", "sequence too large");
+
+        // The budget is shared by all allocations of an object: each string fits into the limit on its own, but
+        //  not together with the others
+        let string = [&[0x04, 0x28][..], &[7; 40]].concat();
+        let strings = [&[0x30, 0x7e][..], &string.repeat(3)].concat();
+        let budget = |max_alloc| options(DecodeLimits { max_alloc, ..DecodeLimits::UNLIMITED });
+        let read_strings = |options| {
+            let sequence = Sequence::decode_with(&strings, options)?;
+            let mut reader = sequence.reader();
+            let strings: [Vec<u8>; 3] = [reader.read()?, reader.read()?, reader.read()?];
+            reader.finish().map(|_| strings)
+        };
+        read_strings(budget(120)).assert("strings within budget");
+        read_strings(budget(100)).map(|_| ()).assert_err("LimitExceeded", "strings exceed budget");
+
+        // The vector itself is drawn from the budget, too
+        let vector_size = 3 * mem::size_of::<Vec<u8>>() + 120;
+        SequenceVec::<Vec<u8>>::decode_with(&strings, budget(vector_size)).map(|_| ()).assert("vector within budget");
+        SequenceVec::<Vec<u8>>::decode_with(&strings, budget(vector_size - 1))
+            .map(|_| ())
+            .assert_err("LimitExceeded", "vector exceeds budget");
+
+        // Subobjects that are not allocated do not consume the budget, regardless of their length
+        let mixed = [&[0x30, 0x81, 0xd2, 0x0c, 0x05][..], b"short", &[0x04, 0x81, 0xc8], &[7; 200]].concat();
+        let sequence = Sequence::decode_with(&mixed, budget(100)).assert("large sequence");
+        sequence.get_as::<String>(0).assert("small string in large sequence");
+        sequence.get_as::<&[u8]>(1).assert("borrowed octet string in large sequence");
+        let mut reader = sequence.reader();
+        reader.read::<String>().assert("small string in large sequence");
+        reader.read::<&[u8]>().assert("borrowed octet string in large sequence");
+        let mut reader = sequence.reader();
+        reader.read::<String>().assert("small string in large sequence");
+        reader.read::<Vec<u8>>().assert_err("LimitExceeded", "large octet string");
+
+        // Nested collections are limited at each level
+        let nested = b"\x30\x06\x30\x04\x30\x02\x30\x00";
+        SequenceVec::<SequenceVec<SequenceVec<()>>>::decode_with(nested, depth(2))
+            .map(|_| ())
+            .assert_err("LimitExceeded", "sequence too deep");
+    }
+}
+
+#[test]
+fn encode_reverse() {
+    // The buffer is too small for the value, the length or the tag
+    for len in [0, 2, 3, 4] {
+        let mut buf = vec![0; len];
+        let writer = ConstructedWriter::sequence().push(7u8);
+        writer.encode_reverse(&mut ReverseSliceSink::new(&mut buf)).assert_err("InOutError", "too small");
     }
 }
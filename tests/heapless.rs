@@ -0,0 +1,69 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(feature = "heapless")]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{DerObject, HeaplessBacking, Sink};
+
+#[test]
+fn sink() {
+    let mut buf: heapless::Vec<u8, 4> = heapless::Vec::new();
+    buf.write(0x05).assert("heapless");
+    buf.write_all(b"\x00\x05").assert("heapless");
+    buf.write_all(b"\x00\x05").assert_err("InOutError", "heapless");
+    buf.write(0x00).assert("heapless");
+    buf.write(0x05).assert_err("InOutError", "heapless");
+    assert_eq!(buf.as_slice(), b"\x05\x00\x05\x00");
+
+    // Copy an object from a source into a fixed-capacity backing
+    let mut buf: heapless::Vec<u8, 8> = heapless::Vec::new();
+    let mut source = b"\x04\x03\x01\x02\x03\x05\x00".iter();
+    let object = DerObject::decode_from_source(&mut source, HeaplessBacking(&mut buf)).assert("heapless");
+    assert_eq!(object.value(), b"\x01\x02\x03");
+
+    let mut buf: heapless::Vec<u8, 4> = heapless::Vec::new();
+    let mut source = b"\x04\x03\x01\x02\x03".iter();
+    DerObject::decode_from_source(&mut source, HeaplessBacking(&mut buf))
+        .map(|_| ())
+        .assert_err("InOutError", "heapless");
+}
+
+#[cfg(feature = "native_types")]
+#[test]
+fn typed() {
+    use asn1_der::typed::{DerDecodable, DerEncodable, HeaplessSequence};
+
+    /// Encodes `value` into a fixed-capacity vector
+    fn encode<T: DerEncodable>(value: &T) -> heapless::Vec<u8, 32> {
+        let mut buf = heapless::Vec::new();
+        value.encode(&mut buf).assert("heapless");
+        assert_eq!(value.encoded_len().assert("heapless"), buf.len());
+        buf
+    }
+
+    // Octet strings
+    let bytes = heapless::Vec::<u8, 3>::decode(b"\x04\x03\x01\x02\x03").assert("heapless");
+    assert_eq!(bytes.as_slice(), b"\x01\x02\x03");
+    assert_eq!(encode(&bytes).as_slice(), b"\x04\x03\x01\x02\x03");
+    heapless::Vec::<u8, 2>::decode(b"\x04\x03\x01\x02\x03").assert_err("Unsupported", "heapless");
+
+    // UTF-8 strings
+    let string = heapless::String::<5>::decode(b"\x0c\x05hello").assert("heapless");
+    assert_eq!(string.as_str(), "hello");
+    assert_eq!(encode(&string).as_slice(), b"\x0c\x05hello");
+    heapless::String::<4>::decode(b"\x0c\x05hello").assert_err("Unsupported", "heapless");
+    heapless::String::<4>::decode(b"\x0c\x01\xff").assert_err("InvalidData", "heapless");
+
+    // Sequences
+    let raw = b"\x30\x09\x02\x01\x01\x02\x01\x02\x02\x01\x03";
+    let sequence = HeaplessSequence::<u8, 3>::decode(raw).assert("heapless");
+    assert_eq!(*sequence, [1, 2, 3]);
+    assert_eq!(encode(&sequence).as_slice(), raw);
+    HeaplessSequence::<u8, 2>::decode(raw).map(|_| ()).assert_err("Unsupported", "heapless");
+    HeaplessSequence::<bool, 3>::decode(raw).map(|_| ()).assert_err("InvalidData", "heapless");
+
+    let nested =
+        HeaplessSequence::<heapless::Vec<u8, 2>, 2>::decode(b"\x30\x06\x04\x01\x07\x04\x01\x08").assert("heapless");
+    assert_eq!((nested[0].as_slice(), nested[1].as_slice()), (b"\x07".as_slice(), b"\x08".as_slice()));
+}
@@ -1,5 +1,5 @@
 use asn1_der::Asn1DerError;
-use asn1_der::Asn1DerErrorVariant::{InOutError, InvalidData, Other, Unsupported};
+use asn1_der::Asn1DerErrorVariant::{InOutError, InvalidData, LimitExceeded, Other, Unsupported};
 
 pub trait OptionExt<T> {
     /// Returns the `Some` variant or pretty prints the error and panics
@@ -48,6 +48,7 @@ impl<T> ResultExt<T, Asn1DerError> for Result<T, Asn1DerError> {
             Err(Asn1DerError { error: InOutError(_), .. }) if variant == "InOutError" => (),
             Err(Asn1DerError { error: InvalidData(_), .. }) if variant == "InvalidData" => (),
             Err(Asn1DerError { error: Unsupported(_), .. }) if variant == "Unsupported" => (),
+            Err(Asn1DerError { error: LimitExceeded(_), .. }) if variant == "LimitExceeded" => (),
             Err(Asn1DerError { error: Other(_), .. }) if variant == "Other" => (),
             Ok(_) => {
                 eprintln!("Unexpected success @\"{}\"; expected {}", name, variant);
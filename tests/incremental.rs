@@ -0,0 +1,72 @@
+#![cfg_attr(test, deny(warnings))]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{DecodeOptions, DerObject, IncrementalParser, Progress};
+use core::ops::Range;
+
+/// A stream of three back-to-back objects
+fn stream() -> (Vec<u8>, Vec<Range<usize>>) {
+    let mut stream = b"\x05\x00\x04\x82\x01\x2c".to_vec();
+    stream.extend((0..300).map(|i| i as u8));
+    stream.extend_from_slice(b"\x02\x01\x07");
+    (stream, vec![0..2, 2..306, 306..309])
+}
+
+/// Feeds `stream` in chunks of `chunk_len` bytes and collects the completed ranges
+fn feed_chunked(stream: &[u8], chunk_len: usize) -> Vec<Range<usize>> {
+    let (mut parser, mut ranges) = (IncrementalParser::new(), Vec::new());
+    for mut chunk in stream.chunks(chunk_len) {
+        while let Progress::Complete { range, consumed } = parser.feed(chunk).assert("chunk") {
+            ranges.push(range);
+            chunk = &chunk[consumed..];
+        }
+    }
+    assert!(parser.is_at_boundary());
+    assert_eq!(parser.position(), stream.len());
+    ranges
+}
+
+#[test]
+fn chunks() {
+    let (stream, expected) = stream();
+    for chunk_len in [1, 2, 3, 5, 7, 64, 256, stream.len()] {
+        assert_eq!(feed_chunked(&stream, chunk_len), expected, "@{}", chunk_len);
+    }
+
+    // The ranges are valid objects
+    for range in expected {
+        DerObject::decode(&stream[range]).assert("range");
+    }
+}
+
+#[test]
+fn need_more() {
+    let mut parser = IncrementalParser::default();
+    assert!(parser.is_at_boundary());
+    assert_eq!(parser.feed(b"").assert("empty"), Progress::NeedMore(2));
+    assert_eq!(parser.feed(b"\x04").assert("tag"), Progress::NeedMore(1));
+    assert!(!parser.is_at_boundary());
+    assert_eq!(parser.feed(b"\x82").assert("length"), Progress::NeedMore(2));
+    assert_eq!(parser.feed(b"\x01").assert("length"), Progress::NeedMore(1));
+    assert_eq!(parser.feed(b"\x2c\x00").assert("value"), Progress::NeedMore(299));
+    assert_eq!(parser.feed(&[0; 298]).assert("value"), Progress::NeedMore(1));
+    assert_eq!(parser.feed(b"\x00\x05\x00").assert("value"), Progress::Complete { range: 0..304, consumed: 1 });
+    assert_eq!(parser.feed(b"\x05\x00").assert("null"), Progress::Complete { range: 304..306, consumed: 2 });
+    assert_eq!(parser.feed(b"").assert("empty"), Progress::NeedMore(2));
+}
+
+#[test]
+fn err() {
+    let mut parser = IncrementalParser::new();
+    parser.feed(b"\x04\x81\x01").assert_err("InvalidData", "non-minimal length");
+    IncrementalParser::new().feed(b"\x04\x89").assert_err("Unsupported", "oversized length");
+
+    // Non-minimal lengths can be allowed explicitly
+    let mut parser = IncrementalParser::with_options(DecodeOptions::LENIENT);
+    let progress = parser.feed(b"\x04\x81\x01\x00").assert("non-minimal length");
+    assert_eq!(progress, Progress::Complete { range: 0..4, consumed: 4 });
+    let mut parser = IncrementalParser::with_options(DecodeOptions::LENIENT);
+    parser.feed(b"\x30\x80\x02\x01\x05\x00\x00").assert_err("InvalidData", "indefinite length");
+}
@@ -0,0 +1,109 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(feature = "std")]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{Asn1DerError, DerObject, IoSink, IoSource, Sink, SliceSink, Source};
+use std::io::{self, ErrorKind, Read};
+
+/// A reader that fails with a custom error after `n` bytes
+struct FailingReader<'a> {
+    bytes: &'a [u8],
+    n: usize,
+}
+impl Read for FailingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.n {
+            0 => Err(io::Error::new(ErrorKind::ConnectionReset, "connection reset")),
+            n => {
+                let len = buf.len().min(n).min(self.bytes.len());
+                buf[..len].copy_from_slice(&self.bytes[..len]);
+                self.bytes = &self.bytes[len..];
+                self.n -= len;
+                Ok(len)
+            }
+        }
+    }
+}
+
+#[test]
+fn source() {
+    let raw = b"\x04\x03\x01\x02\x03\x05\x00";
+    let mut source = IoSource::new(raw.as_slice());
+    assert_eq!(source.read().assert("io"), 0x04);
+
+    let mut buf = [0; 4];
+    source.read_exact(&mut buf).assert("io");
+    assert_eq!(&buf, b"\x03\x01\x02\x03");
+    assert!(source.take_error().is_none());
+    assert_eq!(source.into_inner(), b"\x05\x00");
+
+    // The I/O error is kept
+    let mut source = IoSource::new(FailingReader { bytes: raw, n: 2 });
+    source.read_exact(&mut buf).assert_err("InOutError", "io");
+    assert_eq!(source.take_error().map(|e| e.kind()), Some(ErrorKind::ConnectionReset));
+    assert!(source.take_error().is_none());
+}
+
+#[test]
+fn sink() {
+    let mut sink = IoSink::new(Vec::new());
+    sink.write(0x04).assert("io");
+    sink.write_all(b"\x01\x07").assert("io");
+    assert!(sink.take_error().is_none());
+    assert_eq!(sink.into_inner(), b"\x04\x01\x07");
+
+    // The I/O error is kept
+    let mut buf = [0; 2];
+    let mut sink = IoSink::new(buf.as_mut_slice());
+    sink.write_all(b"\x04\x01\x07").assert_err("InOutError", "io");
+    assert_eq!(sink.take_error().map(|e| e.kind()), Some(ErrorKind::WriteZero));
+}
+
+#[test]
+fn io_result() {
+    // Prefer the I/O error
+    let mut source = IoSource::new(FailingReader { bytes: b"\x04\x03\x01\x02\x03", n: 3 });
+    let (mut buf, mut pos) = ([0; 8], 0);
+    let result = DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut pos));
+    let error = source.io_result(result.map(|_| ())).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ConnectionReset);
+
+    // Fall back to the converted `Asn1DerError`
+    let error = source.io_result(DerObject::decode(b"\x04\x81\x01\x00").map(|_| ())).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.get_ref().is_some_and(|e| e.is::<Asn1DerError>()));
+}
+
+#[cfg(feature = "native_types")]
+#[test]
+fn typed() {
+    use asn1_der::typed::{DerDecodable, DerEncodable, Sequence};
+
+    // Encode two objects into the same writer
+    let mut writer = Vec::new();
+    7u8.encode_to_writer(&mut writer).unwrap();
+    true.encode_to_writer(&mut writer).unwrap();
+    assert_eq!(writer, b"\x02\x01\x07\x01\x01\xff");
+
+    let mut buf = [0; 3];
+    let error = true.encode_to_writer(buf.as_mut_slice().split_at_mut(2).0).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::WriteZero);
+
+    // Decode both objects from the same reader
+    #[cfg(not(feature = "no_panic"))]
+    {
+        let (mut reader, mut buf) = (writer.as_slice(), Vec::new());
+        assert_eq!(u8::decode_from_reader(&mut reader, &mut buf).unwrap(), 7);
+        assert!(bool::decode_from_reader(&mut reader, &mut buf).unwrap());
+        assert!(reader.is_empty());
+
+        let error = u8::decode_from_reader(&mut reader, &mut buf).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        let error = u8::decode_from_reader(b"\x01\x01\xff".as_slice(), &mut buf).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let error = Sequence::decode_from_reader(FailingReader { bytes: b"\x30\x03\x05", n: 3 }, &mut buf).err();
+        assert_eq!(error.map(|e| e.kind()), Some(ErrorKind::ConnectionReset));
+    }
+}
@@ -3,16 +3,16 @@
 pub mod helpers;
 
 use crate::helpers::{test_ok, OptionExt, ResultExt};
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 use asn1_der::VecBacking;
-use asn1_der::{der, DerObject, Sink};
+use asn1_der::{der, DerObject, Header, Sink, SliceSink, Source};
 
 #[test]
 fn length() {
     for test in test_ok::load().length {
         if let Some(value) = test.value {
             // Test valid lengths
-            if value <= usize::max_value() as u64 {
+            if value <= usize::MAX as u64 {
                 // Decode length
                 let len = der::length::decode(&mut test.bytes.iter()).assert(&test.name).assert(&test.name);
                 assert_eq!(len, value as usize, "@\"{}\"", &test.name);
@@ -35,7 +35,7 @@ fn length() {
 fn object() {
     for test in test_ok::load().object {
         // Test-copy the object
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let mut bytes = Vec::new();
             DerObject::decode_from_source(&mut test.bytes.iter(), VecBacking(&mut bytes)).assert(&test.name);
@@ -53,3 +53,77 @@ fn object() {
         assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name)
     }
 }
+
+#[test]
+fn header() {
+    for test in test_ok::load().object {
+        // Decode the header with and without the value bytes
+        let header_len = test.bytes.len() - test.value.len();
+        for bytes in [test.bytes.as_slice(), &test.bytes[..header_len]] {
+            let header = Header::decode(bytes).assert(&test.name);
+            assert_eq!(header.tag, test.tag, "@\"{}\"", &test.name);
+            assert_eq!(header.header_len, header_len, "@\"{}\"", &test.name);
+            assert_eq!(header.value_len, test.value.len(), "@\"{}\"", &test.name);
+            assert_eq!(header.constructed, test.tag & 0x20 != 0, "@\"{}\"", &test.name);
+            assert_eq!(header.object_len().assert(&test.name), test.bytes.len(), "@\"{}\"", &test.name);
+        }
+
+        // Read the header from a source that is positioned at the value afterwards
+        let mut source = test.bytes.iter();
+        Header::decode_from_source(&mut source).assert(&test.name);
+        assert_eq!(source.as_slice(), test.value.as_slice(), "@\"{}\"", &test.name);
+    }
+}
+
+#[test]
+fn bulk() {
+    /// A source that only implements the per-byte `read`
+    struct ByteSource<'a>(std::slice::Iter<'a, u8>);
+    impl Source for ByteSource<'_> {
+        fn read(&mut self) -> Result<u8, asn1_der::Asn1DerError> {
+            self.0.read()
+        }
+    }
+
+    // A large object that is copied in several chunks
+    let mut raw = vec![0x04, 0x83, 0x01, 0x00, 0x01];
+    raw.extend((0..0x010001).map(|i| i as u8));
+
+    // Copy the object via the bulk and via the per-byte implementations
+    let (mut buf, mut pos, mut read) = (vec![0; raw.len()], 0, 0);
+    let mut source = raw.iter().counting_source(&mut read);
+    let object = DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut pos)).assert("bulk");
+    assert_eq!(object.raw(), raw.as_slice());
+    assert_eq!((pos, read), (raw.len(), raw.len()));
+
+    let (mut buf, mut pos) = (vec![0; raw.len()], 0);
+    let mut source = ByteSource(raw.iter());
+    let object = DerObject::decode_from_source(&mut source, SliceSink::new(&mut buf, &mut pos)).assert("per-byte");
+    assert_eq!(object.raw(), raw.as_slice());
+
+    // Encode the object into different sinks
+    let (mut buf, mut written) = (vec![0; raw.len()], 0);
+    object.encode(&mut buf.iter_mut().counting_sink(&mut written)).assert("bulk");
+    assert_eq!((buf.as_slice(), written), (raw.as_slice(), raw.len()));
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+    {
+        let mut buf = Vec::new();
+        object.encode(&mut VecBacking(&mut buf)).assert("bulk");
+        assert_eq!(buf, raw);
+    }
+
+    // Read and write in bulk after single elements
+    let (mut iter, mut head, mut tail) = (raw.iter(), [0; 3], [0; 2]);
+    assert_eq!(iter.read().assert("bulk"), 0x04);
+    iter.read_exact(&mut head).assert("bulk");
+    iter.read_exact(&mut tail).assert("bulk");
+    assert_eq!((head, tail, iter.read().assert("bulk")), ([0x83, 0x01, 0x00], [0x01, 0x00], 0x01));
+
+    let mut buf = [0; 6];
+    let mut sink = buf.iter_mut();
+    sink.write(1).assert("bulk");
+    sink.write_all(&[2, 3, 4]).assert("bulk");
+    sink.write_all(&[]).assert("bulk");
+    sink.write(5).assert("bulk");
+    assert_eq!(buf, [1, 2, 3, 4, 5, 0]);
+}
@@ -4,13 +4,16 @@
 pub mod helpers;
 
 use crate::helpers::{test_ok, ResultExt};
-#[cfg(all(feature = "std", not(feature = "no_panic")))]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
 use asn1_der::typed::SequenceVec;
 use asn1_der::typed::{
-    Boolean, DerDecodable, DerEncodable, DerTypeView, Integer, Null, OctetString, Sequence, Utf8String,
+    Boolean, ConstructedWriter, DefaultValue, Defaulted, DerChoice, DerDecodable, DerEncodable, DerTagged, DerTypeView,
+    Explicit, Implicit, Integer, Null, OctetString, Optional, Sequence, TypeDefault, Utf8String,
 };
-use asn1_der::{DerObject, SliceSink};
+use asn1_der::{DecodeOptions, DerObject, ReverseSink, ReverseSliceSink, SliceSink};
 use core::convert::TryFrom;
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+use std::borrow::Cow;
 
 #[test]
 fn boolean() {
@@ -113,10 +116,17 @@ fn octet_string() {
         let object = OctetString::decode(test.bytes.as_slice()).assert(&test.name);
         assert_eq!(object.get(), test.value.as_slice(), "@\"{}\"", &test.name);
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        let borrowed = <&[u8]>::decode(test.bytes.as_slice()).assert(&test.name);
+        assert_eq!(borrowed, test.value.as_slice(), "@\"{}\"", &test.name);
+        assert_eq!(borrowed.as_ptr(), object.get().as_ptr(), "@\"{}\"", &test.name);
+
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let native = Vec::<u8>::decode(test.bytes.as_slice()).assert(&test.name);
             assert_eq!(native, test.value, "@\"{}\"", &test.name);
+
+            let cow = Cow::<[u8]>::decode(test.bytes.as_slice()).assert(&test.name);
+            assert!(matches!(cow, Cow::Borrowed(value) if value == test.value.as_slice()), "@\"{}\"", &test.name);
         }
 
         // Encode the object
@@ -124,11 +134,19 @@ fn octet_string() {
         object.encode(&mut bytes.iter_mut()).assert(&test.name);
         assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        let mut bytes = vec![0; test.bytes.len()];
+        test.value.as_slice().encode(&mut bytes.iter_mut()).assert(&test.name);
+        assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
+
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let mut bytes = vec![0; test.bytes.len()];
             test.value.encode(&mut bytes.iter_mut()).assert(&test.name);
             assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
+
+            let mut bytes = vec![0; test.bytes.len()];
+            Cow::<[u8]>::Owned(test.value.clone()).encode(&mut bytes.iter_mut()).assert(&test.name);
+            assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
         }
 
         let (mut bytes, mut pos) = ([0; 1024], 0);
@@ -151,7 +169,7 @@ fn sequence() {
             assert_eq!(object.value(), obj.value.as_slice(), "@\"{}\":{}", &test.name, i);
         }
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let native = SequenceVec::<Vec<u8>>::decode(test.bytes.as_slice()).assert(&test.name);
             for (i, obj) in test.sequence.iter().enumerate() {
@@ -164,7 +182,7 @@ fn sequence() {
         object.encode(&mut bytes.iter_mut()).assert(&test.name);
         assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let values: Vec<_> =
                 test.sequence.iter().map(|o| DerObject::decode(o.bytes.as_slice()).assert(&test.name)).collect();
@@ -185,6 +203,172 @@ fn sequence() {
     }
 }
 
+#[test]
+fn constructed_writer() {
+    const EXPECTED: &[u8] = b"\x30\x12\x02\x01\x02\x0c\x04test\xa0\x07\x30\x05\x01\x01\xff\x05\x00";
+
+    // Build a sequence with heterogeneous subobjects
+    let name = DerObject::decode(b"\x0c\x04test").assert("name");
+    let inner = ConstructedWriter::sequence().push(true).push(());
+    let writer = ConstructedWriter::sequence().push(2u8).push(name).push(ConstructedWriter::new(0xa0).push(&inner));
+
+    // Encode the object
+    let mut bytes = vec![0; EXPECTED.len()];
+    writer.encode(&mut bytes.iter_mut()).assert("constructed writer");
+    assert_eq!(bytes, EXPECTED);
+
+    let (mut bytes, mut pos) = ([0; 1024], 0);
+    let object = writer.finish(SliceSink::new(&mut bytes, &mut pos)).assert("constructed writer");
+    assert_eq!(object.raw(), EXPECTED);
+
+    // Decode the object
+    let sequence = Sequence::load(object).assert("constructed writer");
+    assert_eq!(sequence.get_as::<u8>(0).assert("constructed writer"), 2);
+    assert_eq!(sequence.get_as::<Utf8String>(1).assert("constructed writer").get(), "test");
+    assert_eq!(sequence.get(2).assert("constructed writer").tag(), 0xa0);
+
+    // Empty constructions
+    let mut bytes = vec![0; 2];
+    ConstructedWriter::new(0x31).encode(&mut bytes.iter_mut()).assert("empty constructed writer");
+    assert_eq!(bytes, b"\x31\x00");
+}
+
+#[test]
+fn tagged() {
+    const BYTES: &[u8] = b"\x30\x0d\xa0\x03\x02\x01\x07\x81\x01\xff\xa2\x03\x02\x01\x05";
+
+    // Read the tagged subobjects
+    let sequence = Sequence::decode(BYTES).assert("tagged");
+    let mut reader = sequence.reader();
+    assert_eq!(reader.read::<Explicit<0, u8>>().assert("explicit"), Explicit(7));
+    assert_eq!(reader.read::<Implicit<1, bool>>().assert("implicit"), Implicit(true));
+    assert!(reader.read_optional::<Implicit<2, u8>>().assert("optional").is_none());
+    assert_eq!(reader.read_optional::<Explicit<2, u8>>().assert("optional"), Some(Explicit(5)));
+    reader.finish().assert("tagged");
+
+    // Write the tagged subobjects
+    let writer = ConstructedWriter::sequence().push(Explicit::<0, _>(7u8)).push(Implicit::<1, _>(true));
+    let writer = writer.push(Explicit::<2, _>(5u8));
+    let mut bytes = vec![0; BYTES.len()];
+    writer.encode(&mut bytes.iter_mut()).assert("tagged");
+    assert_eq!(bytes, BYTES);
+}
+
+/// The default value `1` for `INTEGER DEFAULT 1`
+struct One;
+impl DefaultValue<u8> for One {
+    fn default_value() -> u8 {
+        1
+    }
+}
+
+#[test]
+fn defaulted() {
+    // `SEQUENCE { version INTEGER DEFAULT 1, cA BOOLEAN DEFAULT FALSE }` with default values
+    let sequence = Sequence::decode(b"\x30\x00").assert("defaulted");
+    let mut reader = sequence.reader();
+    assert_eq!(*reader.read_defaulted::<u8, One>().assert("version").get(), 1);
+    assert!(!*reader.read_defaulted::<bool, TypeDefault>().assert("ca").get());
+    reader.finish().assert("defaulted");
+
+    let writer = ConstructedWriter::sequence().push(Defaulted::<u8, One>::new(1)).push(Defaulted::<bool>::new(false));
+    let mut bytes = vec![0; 2];
+    writer.encode(&mut bytes.iter_mut()).assert("defaulted");
+    assert_eq!(bytes, b"\x30\x00");
+
+    // The same sequence with non-default values
+    const BYTES: &[u8] = b"\x30\x06\x02\x01\x03\x01\x01\xff";
+    let sequence = Sequence::decode(BYTES).assert("defaulted");
+    let mut reader = sequence.reader();
+    assert_eq!(*reader.read_defaulted::<u8, One>().assert("version").get(), 3);
+    assert!(*reader.read_defaulted::<bool, TypeDefault>().assert("ca").get());
+    reader.finish().assert("defaulted");
+
+    let writer = ConstructedWriter::sequence().push(Defaulted::<u8, One>::new(3)).push(Defaulted::<bool>::new(true));
+    let mut bytes = vec![0; BYTES.len()];
+    writer.encode(&mut bytes.iter_mut()).assert("defaulted");
+    assert_eq!(bytes, BYTES);
+}
+
+#[test]
+fn optional() {
+    // `SEQUENCE { a [0] IMPLICIT INTEGER OPTIONAL, b BOOLEAN OPTIONAL, c INTEGER OR NULL }`
+    const BYTES: &[u8] = b"\x30\x05\x01\x01\xff\x05\x00";
+    let sequence = Sequence::decode(BYTES).assert("optional");
+    let mut reader = sequence.reader();
+    assert_eq!(Optional::from(reader.read_optional::<Implicit<0, u8>>().assert("a")), Optional(None));
+    assert_eq!(Optional::from(reader.read_optional::<bool>().assert("b")), Optional(Some(true)));
+    assert_eq!(reader.read::<Option<u8>>().assert("c"), None);
+    reader.finish().assert("optional");
+
+    // Absent optionals are omitted whereas `None` is encoded as `NULL`
+    let writer = ConstructedWriter::sequence().push(Optional::<Implicit<0, u8>>(None)).push(Optional(Some(true)));
+    let writer = writer.push(None::<u8>);
+    let mut bytes = vec![0; BYTES.len()];
+    writer.encode(&mut bytes.iter_mut()).assert("optional");
+    assert_eq!(bytes, BYTES);
+}
+
+/// A manually implemented `CHOICE { bool BOOLEAN, int INTEGER }`
+#[derive(Debug, PartialEq)]
+enum BoolOrInt {
+    Bool(bool),
+    Int(u8),
+}
+impl DerChoice for BoolOrInt {
+    fn matches_tag(tag: u8) -> bool {
+        tag == bool::TAG || tag == u8::TAG
+    }
+}
+impl<'a> DerDecodable<'a> for BoolOrInt {
+    fn load(object: DerObject<'a>) -> Result<Self, asn1_der::Asn1DerError> {
+        match object.tag() {
+            bool::TAG => Ok(Self::Bool(bool::load(object)?)),
+            _ => Ok(Self::Int(u8::load(object)?)),
+        }
+    }
+}
+
+#[test]
+fn choice() {
+    const BYTES: &[u8] = b"\x30\x06\x01\x01\xff\x02\x01\x07";
+
+    // Read the choices by their tags
+    let sequence = Sequence::decode(BYTES).assert("choice");
+    let mut reader = sequence.reader();
+    assert_eq!(reader.read_if::<BoolOrInt, _>(BoolOrInt::matches_tag).assert("bool"), Some(BoolOrInt::Bool(true)));
+    assert_eq!(reader.read_if::<BoolOrInt, _>(BoolOrInt::matches_tag).assert("int"), Some(BoolOrInt::Int(7)));
+    assert_eq!(reader.read_if::<BoolOrInt, _>(BoolOrInt::matches_tag).assert("absent"), None);
+    reader.finish().assert("choice");
+}
+
+/// A type view that is not provided by the crate
+struct BitStringView<'a>(DerObject<'a>);
+impl DerTagged for BitStringView<'_> {
+    const TAG: u8 = b'\x03';
+}
+impl<'a> DerTypeView<'a> for BitStringView<'a> {
+    fn object(&self) -> DerObject<'a> {
+        self.0
+    }
+}
+
+#[test]
+fn type_view() {
+    /// Returns the tag of the type view `T`
+    fn view_tag<'a, T: DerTypeView<'a>>() -> u8 {
+        T::TAG
+    }
+
+    // The tag of a type view is its `DerTagged` tag, which is unambiguous even if both traits are in scope
+    assert_eq!(view_tag::<Boolean>(), Boolean::TAG);
+    assert_eq!(view_tag::<Integer>(), Integer::TAG);
+    assert_eq!(view_tag::<Sequence>(), Sequence::TAG);
+
+    let view = BitStringView(DerObject::decode(b"\x03\x01\x00").assert("type view"));
+    assert_eq!(view.object().tag(), view_tag::<BitStringView>());
+}
+
 #[test]
 fn utf8_string() {
     for test in test_ok::load().typed.utf8_string {
@@ -192,10 +376,16 @@ fn utf8_string() {
         let object = Utf8String::decode(test.bytes.as_slice()).assert(&test.name);
         assert_eq!(object.get(), test.utf8str.as_str(), "@\"{}\"", &test.name);
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        let borrowed = <&str>::decode(test.bytes.as_slice()).assert(&test.name);
+        assert_eq!(borrowed, test.utf8str.as_str(), "@\"{}\"", &test.name);
+
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let native = String::decode(test.bytes.as_slice()).assert(&test.name);
             assert_eq!(native, test.utf8str, "@\"{}\"", &test.name);
+
+            let cow = Cow::<str>::decode(test.bytes.as_slice()).assert(&test.name);
+            assert!(matches!(cow, Cow::Borrowed(value) if value == test.utf8str), "@\"{}\"", &test.name);
         }
 
         // Encode the object
@@ -203,11 +393,19 @@ fn utf8_string() {
         object.encode(&mut bytes.iter_mut()).assert(&test.name);
         assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
 
-        #[cfg(all(feature = "std", not(feature = "no_panic")))]
+        let mut bytes = vec![0; test.bytes.len()];
+        test.utf8str.as_str().encode(&mut bytes.iter_mut()).assert(&test.name);
+        assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
+
+        #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
         {
             let mut bytes = vec![0; test.bytes.len()];
             test.utf8str.encode(&mut bytes.iter_mut()).assert(&test.name);
             assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
+
+            let mut bytes = vec![0; test.bytes.len()];
+            Cow::<str>::Owned(test.utf8str.clone()).encode(&mut bytes.iter_mut()).assert(&test.name);
+            assert_eq!(bytes, test.bytes, "@\"{}\"", &test.name);
         }
 
         let (mut bytes, mut pos) = ([0; 1024], 0);
@@ -216,3 +414,154 @@ fn utf8_string() {
         assert_eq!(&bytes[..pos], test.bytes.as_slice(), "@\"{}\"", &test.name);
     }
 }
+
+#[test]
+fn decode_options() {
+    // Each laxness is enabled by its own flag
+    let options = DecodeOptions { allow_ber_booleans: true, ..DecodeOptions::STRICT };
+    assert!(bool::decode_with(b"\x01\x01\x01", options).assert("BER boolean"));
+    assert!(Boolean::decode_with(b"\x01\x01\x7f", options).assert("BER boolean").get());
+
+    let options = DecodeOptions { allow_non_minimal_integers: true, ..DecodeOptions::STRICT };
+    assert_eq!(u16::decode_with(b"\x02\x03\x00\x00\xff", options).assert("non-minimal integer"), 255);
+    let integer = Integer::decode_with(b"\x02\x03\xff\xff\x80", options).assert("non-minimal integer");
+    assert_eq!(integer.copy_numbytes([0; 2]).assert("non-minimal integer"), [0xff, 0x80]);
+
+    let options = DecodeOptions { allow_non_minimal_lengths: true, ..DecodeOptions::STRICT };
+    let object = DerObject::decode_with(b"\x04\x82\x00\x01\x07", options).assert("non-minimal length");
+    assert_eq!(object.value(), b"\x07");
+
+    // The options are applied to nested objects
+    let options = DecodeOptions::LENIENT;
+    let sequence = Sequence::decode_with(b"\x30\x81\x08\x02\x82\x00\x01\x07\x01\x01\x01", options).assert("lenient");
+    assert_eq!(sequence.get_as::<u8>(0).assert("lenient"), 7);
+    let mut reader = sequence.reader();
+    assert_eq!(reader.read::<u8>().assert("lenient"), 7);
+    assert!(reader.read::<bool>().assert("lenient"));
+    reader.finish().assert("lenient");
+
+    let explicit = Explicit::<0, bool>::decode_with(b"\xa0\x03\x01\x01\x01", options).assert("lenient");
+    assert!(explicit.0);
+    let implicit = Implicit::<0, u8>::decode_with(b"\x80\x02\x00\x07", options).assert("lenient");
+    assert_eq!(implicit.0, 7);
+    let optional = Optional::<bool>::decode_with(b"\x01\x01\x01", options).assert("lenient");
+    assert_eq!(optional, Optional(Some(true)));
+
+    // Strict DER is the default
+    assert_eq!(DecodeOptions::default(), DecodeOptions::STRICT);
+}
+
+#[test]
+fn encoded_len() {
+    /// Asserts that `value.encoded_len()` is the length of the encoding of `value`
+    fn assert_len<T: DerEncodable>(value: T, name: &str) {
+        let (mut bytes, mut pos) = ([0; 4096], 0);
+        value.encode(&mut SliceSink::new(&mut bytes, &mut pos)).assert(name);
+        assert_eq!(value.encoded_len().assert(name), pos, "@\"{}\"", name);
+    }
+
+    let test = test_ok::load();
+    for test in test.object {
+        assert_len(DerObject::decode(&test.bytes).assert(&test.name), &test.name);
+    }
+    for test in test.typed.bool {
+        assert_len(Boolean::decode(&test.bytes).assert(&test.name), &test.name);
+        assert_len(test.bool, &test.name);
+    }
+    for test in test.typed.integer {
+        assert_len(Integer::decode(&test.bytes).assert(&test.name), &test.name);
+        if let Some(uint) = test.uint {
+            assert_len(uint, &test.name);
+            assert_len(u128::MAX - uint, &test.name);
+        }
+    }
+    for test in test.typed.sequence {
+        let sequence = Sequence::decode(&test.bytes).assert(&test.name);
+        assert_len(sequence, &test.name);
+        assert_len(ConstructedWriter::sequence().push(sequence).push(sequence), &test.name);
+    }
+
+    // Wrappers, optional and default values as well as long lengths
+    assert_len((), "null");
+    assert_len(Option::<u8>::None, "null");
+    assert_len(Optional::<u8>(None), "optional");
+    assert_len(Optional(Some(7u8)), "optional");
+    assert_len(Defaulted::<bool>::new(false), "defaulted");
+    assert_len(Defaulted::<bool>::new(true), "defaulted");
+    assert_len(Explicit::<3, _>(Implicit::<1, _>(7u64)), "tagged");
+    assert_len(ConstructedWriter::sequence(), "constructed");
+    let long = [1u32; 100];
+    assert_len(ConstructedWriter::new(0xa0).push(ConstructedWriter::sequence().push(long[0])), "constructed");
+    let (mut bytes, mut pos) = ([0; 1024], 0);
+    Sequence::write(&long, &mut SliceSink::new(&mut bytes, &mut pos)).assert("sequence");
+    assert_eq!(Sequence::write_len(&long).assert("sequence"), pos);
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+    {
+        assert_len(SequenceVec(long.to_vec()), "sequence");
+        assert_len(vec![7u8; 300], "octet string");
+        assert_len("a".repeat(3000), "UTF-8 string");
+    }
+}
+
+#[test]
+fn encode_reverse() {
+    /// Asserts that `value.encode_reverse` creates the same encoding as `value.encode`
+    fn assert_reverse<T: DerEncodable>(value: T, name: &str) {
+        let (mut bytes, mut pos) = ([0; 4096], 0);
+        value.encode(&mut SliceSink::new(&mut bytes, &mut pos)).assert(name);
+
+        // Encode the value twice to ensure that it is prepended to the already written bytes
+        let mut reverse = [0; 8192];
+        let mut sink = ReverseSliceSink::new(&mut reverse);
+        value.encode_reverse(&mut sink).assert(name);
+        value.encode_reverse(&mut sink).assert(name);
+        assert_eq!(sink.written_mut().len(), pos * 2, "@\"{}\"", name);
+        assert_eq!(&reverse[8192 - pos..], &bytes[..pos], "@\"{}\"", name);
+        assert_eq!(&reverse[8192 - 2 * pos..8192 - pos], &bytes[..pos], "@\"{}\"", name);
+    }
+
+    let test = test_ok::load();
+    for test in test.typed.bool {
+        assert_reverse(Boolean::decode(&test.bytes).assert(&test.name), &test.name);
+        assert_reverse(test.bool, &test.name);
+    }
+    for test in test.typed.integer {
+        if let Some(uint) = test.uint {
+            assert_reverse(uint, &test.name);
+        }
+    }
+    for test in test.typed.sequence {
+        let sequence = Sequence::decode(&test.bytes).assert(&test.name);
+        assert_reverse(sequence, &test.name);
+        assert_reverse(ConstructedWriter::sequence().push(sequence).push(7u8), &test.name);
+    }
+
+    // Wrappers, optional and default values as well as long lengths
+    assert_reverse(Option::<u8>::None, "null");
+    assert_reverse(Optional(Some(7u8)), "optional");
+    assert_reverse(ConstructedWriter::sequence().push(Optional::<u8>(None)), "optional");
+    assert_reverse(ConstructedWriter::sequence().push(Defaulted::<bool>::new(false)), "defaulted");
+    assert_reverse(Explicit::<3, _>(Implicit::<1, _>(7u64)), "tagged");
+    assert_reverse(Implicit::<1, _>(ConstructedWriter::sequence().push(true)), "tagged");
+    assert_reverse(ConstructedWriter::sequence().push(Implicit::<1, _>(Optional::<u8>(None))), "tagged");
+    let long = [1u32 << 31; 100];
+    assert_reverse(ConstructedWriter::new(0xa0).push(ConstructedWriter::sequence().push(long[0])), "constructed");
+    let mut reverse = [0; 1024];
+    let mut sink = ReverseSliceSink::new(&mut reverse);
+    Sequence::write_reverse(&long, &mut sink).assert("sequence");
+    assert_eq!(sink.written_mut().len(), Sequence::write_len(&long).assert("sequence"));
+    #[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+    {
+        assert_reverse(SequenceVec(long.to_vec()), "sequence");
+        assert_reverse(vec![7u8; 300], "octet string");
+    }
+
+    // Encode into a fixed buffer and get a view over the filled tail
+    let mut buf = [0; 16];
+    let object = ConstructedWriter::sequence()
+        .push(7u8)
+        .push(true)
+        .der_object_reverse(ReverseSliceSink::new(&mut buf))
+        .assert("der object");
+    assert_eq!(object.raw(), b"\x30\x06\x02\x01\x07\x01\x01\xff");
+}
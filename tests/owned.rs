@@ -0,0 +1,69 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(all(feature = "alloc", not(feature = "no_panic")))]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{DecodeOptions, DerObject, DerObjectBuf};
+
+#[test]
+fn object() {
+    // The owned object outlives the decoded data
+    let owned = {
+        let raw = b"\x04\x03\x01\x02\x03\x05\x00".to_vec();
+        DerObject::decode_at(&raw, 0).assert("owned").to_buf()
+    };
+    assert_eq!(owned.raw(), b"\x04\x03\x01\x02\x03");
+    assert_eq!(
+        (owned.tag(), owned.header(), owned.value()),
+        (0x04, b"\x04\x03".as_slice(), b"\x01\x02\x03".as_slice())
+    );
+    assert_eq!(owned.object().raw(), owned.raw());
+
+    // Boxed backings
+    let boxed: DerObjectBuf<Box<[u8]>> = owned.clone().into();
+    assert_eq!(boxed.object().value(), owned.value());
+    let boxed = DerObjectBuf::decode(b"\x05\x00".to_vec().into_boxed_slice()).assert("owned");
+    assert_eq!(boxed.into_inner().as_ref(), b"\x05\x00");
+
+    // Lenient objects keep their header
+    let lenient = DerObjectBuf::decode_with(b"\x04\x81\x01\x07".to_vec(), DecodeOptions::LENIENT).assert("owned");
+    assert_eq!((lenient.header(), lenient.value()), (b"\x04\x81\x01".as_slice(), b"\x07".as_slice()));
+
+    // Invalid or trailing data
+    DerObjectBuf::decode(b"\x04\x81\x01\x07".to_vec()).assert_err("InvalidData", "owned");
+    DerObjectBuf::decode(b"\x05\x00\x05\x00".to_vec()).assert_err("InvalidData", "owned");
+    DerObjectBuf::decode(b"\x04\x02\x01".to_vec()).assert_err("InOutError", "owned");
+}
+
+#[cfg(feature = "native_types")]
+#[test]
+fn typed() {
+    use asn1_der::typed::{
+        Boolean, DerDecodable, DerEncodable, Integer, OctetStringBuf, Sequence, SequenceBuf, Utf8String, Utf8StringBuf,
+    };
+
+    // Owned views outlive the decoded data
+    let (string, sequence) = {
+        let raw = b"\x30\x0b\x0c\x03abc\x01\x01\xff\x02\x01\x07".to_vec();
+        let sequence = Sequence::decode(&raw).assert("owned");
+        (sequence.get_as::<Utf8String>(0).assert("owned").to_buf(), sequence.to_buf())
+    };
+    assert_eq!(string.view().get(), "abc");
+    assert!(sequence.view().get_as::<Boolean>(1).assert("owned").get());
+    assert_eq!(sequence.view().get_as::<Integer>(2).assert("owned").get_numbytes(), b"\x07");
+
+    // Owned types are de- and encodable
+    let mut encoded = Vec::new();
+    sequence.encode(&mut encoded).assert("owned");
+    assert_eq!(encoded.len(), sequence.encoded_len().assert("owned"));
+    assert_eq!(SequenceBuf::decode(&encoded).assert("owned"), sequence);
+    assert_eq!(Utf8StringBuf::decode(b"\x0c\x03abc").assert("owned"), string);
+    OctetStringBuf::decode(b"\x0c\x03abc").assert_err("InvalidData", "owned");
+
+    let object = DerObjectBuf::<Vec<u8>>::decode(b"\x05\x00".to_vec()).assert("owned");
+    let mut encoded = Vec::new();
+    object.encode(&mut encoded).assert("owned");
+    assert_eq!(encoded, b"\x05\x00");
+    assert_eq!(DerObjectBuf::<Box<[u8]>>::load(object.object()).assert("owned").raw(), b"\x05\x00");
+}
@@ -0,0 +1,113 @@
+#![cfg_attr(test, deny(warnings))]
+
+pub mod helpers;
+
+use crate::helpers::ResultExt;
+use asn1_der::{DerObject, PatchingEncoder, SliceSink};
+
+/// Builds the DER encoding of a constructed object with `tag` and `value` by hand
+fn constructed(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut der = vec![tag];
+    match value.len() {
+        len if len < 0x80 => der.push(len as u8),
+        len if len <= 0xff => der.extend_from_slice(&[0x81, len as u8]),
+        len => der.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]),
+    }
+    der.extend_from_slice(value);
+    der
+}
+
+#[test]
+fn nested() {
+    // Lengths that need one, two and three bytes
+    for len in [0, 0x7f, 0x80, 0xff, 0x100, 0x1234] {
+        let payload = vec![0x42; len];
+        let inner = constructed(0x04, &payload);
+        let middle = constructed(0xa0, &constructed(0x30, &inner));
+        let mut expected = constructed(0x02, b"\x07");
+        expected.extend_from_slice(&middle);
+        expected.extend_from_slice(&constructed(0x01, b"\xff"));
+        let expected = constructed(0x30, &expected);
+
+        let (mut buf, mut pos) = (vec![0; expected.len()], 0);
+        let mut sink = SliceSink::new(&mut buf, &mut pos);
+        PatchingEncoder::new(&mut sink)
+            .sequence(|encoder| {
+                encoder.write(0x02, b"\x07")?;
+                encoder.constructed(0xa0, |encoder| encoder.sequence(|encoder| encoder.write(0x04, &payload)))?;
+                encoder.write(0x01, b"\xff")
+            })
+            .assert("nested");
+        assert_eq!(pos, expected.len());
+        assert_eq!(buf, expected);
+
+        let object = DerObject::decode(&buf).assert("nested");
+        assert_eq!(object.raw(), expected.as_slice());
+    }
+}
+
+#[test]
+fn empty() {
+    let (mut buf, mut pos) = ([0; 4], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    PatchingEncoder::new(&mut sink).set(|encoder| encoder.sequence(|_| Ok(()))).assert("empty");
+    assert_eq!(&buf[..pos], b"\x31\x02\x30\x00");
+}
+
+#[test]
+#[cfg(feature = "native_types")]
+fn encode() {
+    use asn1_der::typed::{DerEncodable, Sequence};
+
+    let values = [1u64 << 40; 40];
+    let (mut expected, mut expected_len) = ([0; 512], 0);
+    Sequence::write(&values, &mut SliceSink::new(&mut expected, &mut expected_len)).assert("encode");
+    assert!(Sequence::write_len(&values).assert("encode") > 0xff);
+
+    let (mut buf, mut pos) = ([0; 512], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    PatchingEncoder::new(&mut sink)
+        .sequence(|encoder| values.iter().try_for_each(|value| encoder.encode(value)))
+        .assert("encode");
+    assert_eq!(&buf[..pos], &expected[..expected_len]);
+    assert_eq!(values[0].encoded_len().assert("encode") * values.len() + 4, pos);
+}
+
+#[test]
+#[cfg(all(feature = "alloc", not(feature = "no_panic")))]
+fn vec_backing() {
+    use asn1_der::VecBacking;
+
+    let payload = vec![0x42; 300];
+    let expected = constructed(0x30, &constructed(0x04, &payload));
+
+    let mut buf = b"prefix".to_vec();
+    let mut sink = VecBacking(&mut buf);
+    PatchingEncoder::new(&mut sink).sequence(|encoder| encoder.write(0x04, &payload)).assert("vec backing");
+    assert_eq!(&buf[..6], b"prefix");
+    assert_eq!(&buf[6..], expected.as_slice());
+}
+
+#[test]
+fn err() {
+    // The buffer is too small for the grown length
+    let (mut buf, mut pos) = ([0; 0x82], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    PatchingEncoder::new(&mut sink)
+        .sequence(|encoder| encoder.write(0x04, &[0; 0x7e]))
+        .assert_err("InOutError", "too small");
+
+    // The buffer is too small for the contents
+    let (mut buf, mut pos) = ([0; 4], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    PatchingEncoder::new(&mut sink)
+        .sequence(|encoder| encoder.write(0x04, b"\x01\x02\x03"))
+        .assert_err("InOutError", "too small");
+
+    // Errors of the contents are propagated
+    let (mut buf, mut pos) = ([0; 4], 0);
+    let mut sink = SliceSink::new(&mut buf, &mut pos);
+    PatchingEncoder::new(&mut sink)
+        .sequence(|encoder| encoder.sequence(|_| Err(asn1_der::einval!("Invalid contents"))))
+        .assert_err("InvalidData", "contents");
+}
@@ -0,0 +1,61 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(all(feature = "bytes", not(feature = "no_panic")))]
+
+pub mod helpers;
+
+use crate::helpers::{OptionExt, ResultExt};
+use asn1_der::{DecodeOptions, DerObject, SharedDerObject, Sink};
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn shared() {
+    let mut frame = Bytes::from_static(b"\x30\x07\x04\x03\x01\x02\x03\x05\x00\x02\x01");
+    let object = SharedDerObject::decode_next(&mut frame).assert("shared");
+    assert_eq!(frame.as_ref(), b"\x02\x01");
+    assert_eq!((object.tag(), object.header().as_ref()), (0x30, b"\x30\x07".as_slice()));
+
+    // The value and the children are slices of the frame
+    let value = object.value();
+    assert_eq!(value.as_ptr(), object.raw()[2..].as_ptr());
+    let children: Vec<_> = object.children().map(|child| child.assert("shared")).collect();
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].value().as_ref(), b"\x01\x02\x03");
+    assert_eq!(children[0].value().as_ptr(), value[2..].as_ptr());
+    assert_eq!(children[1].object().raw(), b"\x05\x00");
+
+    // The children are decoded with the options of the parent
+    let lenient = Bytes::from_static(b"\x30\x04\x04\x81\x01\x07");
+    let strict = SharedDerObject::decode(lenient.clone()).assert("shared");
+    strict.children().next().assert("shared").assert_err("InvalidData", "shared");
+    let object = SharedDerObject::decode_with(lenient, DecodeOptions::LENIENT).assert("shared");
+    assert_eq!(object.children().next().assert("shared").assert("shared").value().as_ref(), b"\x07");
+}
+
+#[test]
+fn shared_err() {
+    // Trailing data and truncated objects
+    SharedDerObject::decode(Bytes::from_static(b"\x05\x00\x05\x00")).assert_err("InvalidData", "shared");
+    let mut frame = Bytes::from_static(b"\x04\x02\x01");
+    SharedDerObject::decode_next(&mut frame).assert_err("InOutError", "shared");
+    assert_eq!(frame.len(), 3);
+
+    // The children stop after the first error
+    let object = SharedDerObject::decode(Bytes::from_static(b"\x30\x03\x05\x00\x04")).assert("shared");
+    let mut children = object.children();
+    children.next().assert("shared").assert("shared");
+    children.next().assert("shared").assert_err("InOutError", "shared");
+    assert!(children.next().is_none());
+}
+
+#[test]
+fn sink() {
+    let mut buf = BytesMut::new();
+    buf.write(0x05).assert("shared");
+    buf.write_all(b"\x00\x02\x01\x07").assert("shared");
+    DerObject::decode(b"\x01\x01\xff").assert("shared").encode(&mut buf).assert("shared");
+
+    let mut frame = buf.freeze();
+    let objects: Vec<_> = (0..3).map(|_| SharedDerObject::decode_next(&mut frame).assert("shared")).collect();
+    assert_eq!(objects[2].raw().as_ref(), b"\x01\x01\xff");
+    assert!(frame.is_empty());
+}
@@ -0,0 +1,82 @@
+#![cfg_attr(test, deny(warnings))]
+#![cfg(all(feature = "tokio_io", not(feature = "no_panic")))]
+
+use asn1_der::{Asn1DerError, AsyncDerReader, DecodeOptions, DerObject};
+use std::io::ErrorKind;
+use tokio::io::{self, AsyncWriteExt};
+
+/// Builds an OCTET STRING with `len` value bytes
+fn octet_string(len: usize) -> Vec<u8> {
+    let mut object = vec![0x04, 0x82, (len >> 8) as u8, len as u8];
+    object.extend((0..len).map(|i| i as u8));
+    object
+}
+
+#[tokio::test]
+async fn back_to_back() {
+    let (mut client, server) = io::duplex(64);
+    let messages = [b"\x05\x00".to_vec(), octet_string(1000), b"\x02\x01\x07".to_vec()];
+
+    // Write the messages concurrently so that they arrive in several chunks
+    let writer = {
+        let messages = messages.clone();
+        tokio::spawn(async move {
+            for message in messages {
+                client.write_all(&message).await.unwrap();
+            }
+        })
+    };
+
+    let mut reader = AsyncDerReader::new(server);
+    for message in &messages {
+        let object = reader.read_object().await.unwrap().unwrap();
+        assert_eq!(&object, message);
+        assert_eq!(DerObject::decode(&object).unwrap().raw(), message.as_slice());
+    }
+    writer.await.unwrap();
+    assert!(reader.read_object().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn max_len() {
+    let (mut client, server) = io::duplex(4096);
+    client.write_all(&octet_string(1000)).await.unwrap();
+    client.write_all(&octet_string(996)).await.unwrap();
+
+    // The header is counted into the object length
+    let mut reader = AsyncDerReader::with_max_len(server, 1000);
+    let error = reader.read_object().await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(reader.max_len(), 1000);
+
+    // Huge lengths are rejected before allocating
+    let (mut client, server) = io::duplex(64);
+    client.write_all(b"\x04\x88\x7f\xff\xff\xff\xff\xff\xff\xff").await.unwrap();
+    let error = AsyncDerReader::new(server).read_object().await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+async fn err() {
+    // Truncated objects
+    for truncated in [b"\x04".as_slice(), b"\x04\x82\x01", b"\x04\x03\x01\x02"] {
+        let (mut client, server) = io::duplex(64);
+        client.write_all(truncated).await.unwrap();
+        drop(client);
+        let error = AsyncDerReader::new(server).read_object().await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    // Invalid lengths
+    let (mut client, server) = io::duplex(64);
+    client.write_all(b"\x04\x81\x01\x00").await.unwrap();
+    let error = AsyncDerReader::new(server).read_object().await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.get_ref().is_some_and(|e| e.is::<Asn1DerError>()));
+
+    // ... unless they are explicitly allowed
+    let (mut client, server) = io::duplex(64);
+    client.write_all(b"\x04\x81\x01\x00").await.unwrap();
+    let object = AsyncDerReader::new(server).read_object_with(DecodeOptions::LENIENT).await.unwrap().unwrap();
+    assert_eq!(object, b"\x04\x81\x01\x00");
+}
@@ -0,0 +1,137 @@
+#![cfg_attr(test, deny(warnings))]
+
+pub mod helpers;
+
+use crate::helpers::{OptionExt, ResultExt};
+use asn1_der::traversal::{Node, SliceTraversal};
+
+/// A `SEQUENCE` with a huge `OCTET STRING`, a nested `SEQUENCE` and a `BOOLEAN`
+fn structure() -> Vec<u8> {
+    let mut raw = b"\x30\x83\x01\x86\xaf\x04\x83\x01\x86\xa0".to_vec();
+    raw.extend((0..100_000).map(|i| i as u8));
+    raw.extend_from_slice(b"\x30\x05\x02\x01\x07\x05\x00\x01\x01\xff");
+    raw
+}
+
+/// Asserts that `nodes` have the expected tags and offsets
+fn assert_nodes(nodes: &[Node], expected: &[(u8, u64)]) {
+    let nodes: Vec<_> = nodes.iter().map(|node| (node.header.tag, node.offset)).collect();
+    assert_eq!(nodes, expected);
+}
+
+#[test]
+fn slice() {
+    let raw = structure();
+    let traversal = SliceTraversal::new(&raw);
+
+    // Walk the tree
+    let root = traversal.root().assert("root");
+    assert!(root.header.constructed);
+    assert_eq!((root.value_offset().assert("root"), root.end().assert("root")), (5, raw.len() as u64));
+    let children: Vec<_> = traversal.children(&root).assert("root").map(|child| child.assert("child")).collect();
+    assert_nodes(&children, &[(0x04, 5), (0x30, 100_010), (0x01, 100_017)]);
+
+    let nested: Vec<_> = traversal.children(&children[1]).assert("nested").map(|child| child.assert("child")).collect();
+    assert_nodes(&nested, &[(0x02, 100_012), (0x05, 100_015)]);
+
+    // Load subtrees
+    assert_eq!(traversal.object(&children[1]).assert("object").raw(), b"\x30\x05\x02\x01\x07\x05\x00");
+    assert_eq!(traversal.object(&children[0]).assert("object").value().len(), 100_000);
+    assert_eq!(traversal.node_at(100_017).assert("node").header.value_len, 1);
+}
+
+#[test]
+fn slice_err() {
+    let raw = structure();
+    let traversal = SliceTraversal::new(&raw[..raw.len() - 1]);
+    traversal.root().assert_err("InOutError", "truncated root");
+    traversal.node_at(raw.len() as u64).assert_err("InOutError", "offset beyond end");
+
+    // A child that exceeds its parent
+    let traversal = SliceTraversal::new(b"\x30\x03\x04\x02\x01\x07");
+    let node = Node { offset: 0, header: asn1_der::Header::decode(b"\x30\x03").assert("header") };
+    let mut children = traversal.children(&node).assert("children");
+    children.next().assert("child").assert_err("InvalidData", "child exceeds parent");
+    assert!(children.next().is_none());
+}
+
+#[cfg(all(feature = "std", not(feature = "no_panic")))]
+mod seek {
+    use super::{assert_nodes, structure};
+    use asn1_der::traversal::SeekTraversal;
+    use std::io::{self, Cursor, ErrorKind, Read, Seek, SeekFrom};
+
+    /// A reader that counts the bytes read
+    struct Counting<R> {
+        inner: R,
+        read: usize,
+    }
+    impl<R: Read> Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.read += read;
+            Ok(read)
+        }
+    }
+    impl<R: Seek> Seek for Counting<R> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn traverse() {
+        let raw = structure();
+        let mut traversal = SeekTraversal::new(Counting { inner: Cursor::new(&raw), read: 0 });
+
+        // Walk the tree without reading the values
+        let root = traversal.root().unwrap();
+        let children: Vec<_> = traversal.children(&root).unwrap().map(Result::unwrap).collect();
+        assert_nodes(&children, &[(0x04, 5), (0x30, 100_010), (0x01, 100_017)]);
+        let nested: Vec<_> = traversal.children(&children[1]).unwrap().map(Result::unwrap).collect();
+        assert_nodes(&nested, &[(0x02, 100_012), (0x05, 100_015)]);
+
+        // Load a single subtree
+        let mut buf = Vec::new();
+        let object = traversal.object(&children[1], &mut buf).unwrap();
+        assert_eq!(object.raw(), b"\x30\x05\x02\x01\x07\x05\x00");
+        assert!(traversal.into_inner().read < 64);
+    }
+
+    #[test]
+    fn err() {
+        let raw = structure();
+        let mut traversal = SeekTraversal::new(Cursor::new(&raw[..raw.len() - 1]));
+        let root = traversal.root().unwrap();
+        let error = traversal.object(&root, &mut Vec::new()).map(|_| ()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Other);
+
+        // The I/O error is kept
+        let error = traversal.node_at(raw.len() as u64).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+        // A child that exceeds its parent
+        let mut traversal = SeekTraversal::new(Cursor::new(b"\x30\x03\x04\x02\x01\x07"));
+        let root = traversal.root().unwrap();
+        let error = traversal.children(&root).unwrap().next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
+
+#[cfg(feature = "memmap2")]
+#[test]
+fn mmap() {
+    let path = std::env::temp_dir().join(format!("asn1_der-traversal-{}.der", std::process::id()));
+    std::fs::write(&path, structure()).unwrap();
+
+    let file = std::fs::File::open(&path).unwrap();
+    // SAFETY: The file is private to this test and not modified while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+    let traversal = SliceTraversal::from(&mmap);
+    let root = traversal.root().assert("root");
+    let last = traversal.children(&root).assert("root").last().assert("last").assert("last");
+    assert_eq!(traversal.object(&last).assert("last").raw(), b"\x01\x01\xff");
+
+    drop(mmap);
+    std::fs::remove_file(&path).unwrap();
+}